@@ -0,0 +1,369 @@
+//! # lcd-sim
+//! terminal simulator: drives a `LiquidCrystal` against the HD44780 emulator
+//! with the commands of a script or of stdin
+//!
+//! ```text
+//! cargo run --features std --bin lcd-sim -- --layout 20x4 menu.txt
+//! ```
+use std::io::{self, BufRead, IsTerminal};
+use std::time::Duration;
+
+use liquid_crystal::emulator::*;
+use liquid_crystal::prelude::*;
+use liquid_crystal::BusBits;
+
+const HELP: &str = "\
+usage: lcd-sim [--layout 16x2|20x4|20x4-4line|20x3-top|20x3-middle|20x3-bottom|20x2-double]
+               [--bus 4|8] [--blue] [--snapshot] [script]
+
+commands (one per line, # for comments):
+  text <text>             writes the text
+  cursor <line> <column>  moves the cursor
+  char <slot> <rows>      creates a custom character, rows separated by commas (0x1f,0x11,...)
+  put <slot>              writes a custom character
+  clear | home
+  scroll left|right       shifts the display
+  cursor on|off | blink on|off | display on|off | autoscroll on|off
+  wait <ms>               waits before the next command
+  quit";
+
+struct Options {
+    layout: String,
+    bus: BusBits,
+    backlight: Backlight,
+    snapshot: bool,
+    script: Option<String>,
+}
+
+fn main() {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n{}", message, HELP);
+            std::process::exit(2);
+        }
+    };
+    let result = match options.layout.as_str() {
+        "16x2" => run(LCD16X2, &options),
+        "20x4" => run(LCD20X4, &options),
+        "20x4-4line" => run(LCD20X4_4LINE, &options),
+        "20x3-top" => run(LCD20X3_DOUBLE_TOP, &options),
+        "20x3-middle" => run(LCD20X3_DOUBLE_MIDDLE, &options),
+        "20x3-bottom" => run(LCD20X3_DOUBLE_BOTTOM, &options),
+        "20x2-double" => run(LCD20X2_DOUBLE, &options),
+        layout => Err(format!("unknown layout {}", layout)),
+    };
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        layout: "16x2".to_string(),
+        bus: BusBits::Bus4Bits,
+        backlight: Backlight::Green,
+        snapshot: false,
+        script: None,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--layout" => options.layout = args.next().ok_or("--layout needs a value")?,
+            "--bus" => {
+                options.bus = match args.next().as_deref() {
+                    Some("4") => BusBits::Bus4Bits,
+                    Some("8") => BusBits::Bus8Bits,
+                    _ => return Err("--bus is 4 or 8".to_string()),
+                }
+            }
+            "--blue" => options.backlight = Backlight::Blue,
+            "--snapshot" => options.snapshot = true,
+            "--help" | "-h" => return Err(String::new()),
+            _ if options.script.is_none() && !arg.starts_with("--") => options.script = Some(arg),
+            _ => return Err(format!("unknown argument {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn run<const COLS: u8, const LINES: usize>(
+    layout: Layout<COLS, LINES>,
+    options: &Options,
+) -> Result<(), String> {
+    let script = match &options.script {
+        Some(path) => {
+            Some(std::fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?)
+        }
+        None => None,
+    };
+    let interactive = script.is_none() && io::stdin().is_terminal();
+    let lines: Box<dyn Iterator<Item = String>> = match script {
+        Some(script) => Box::new(
+            script
+                .lines()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ),
+        None => Box::new(io::stdin().lock().lines().map_while(Result::ok)),
+    };
+
+    let mut emulator = HD44780::new();
+    let mut delay = VirtualDelay::default();
+    let mut lcd = LiquidCrystal::new(&mut emulator, options.bus, layout);
+    lcd.begin(&mut delay);
+    let live = !options.snapshot;
+    if live {
+        draw(lcd.interface(), &layout, options.backlight, "");
+    }
+
+    for (number, line) in lines.enumerate() {
+        let line = line.trim_end();
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let status = match execute(&mut lcd, &mut delay, line) {
+            Ok(true) => break,
+            Ok(false) => String::new(),
+            Err(message) if interactive => message,
+            Err(message) => return Err(format!("line {}: {}", number + 1, message)),
+        };
+        if live {
+            draw(lcd.interface(), &layout, options.backlight, &status);
+        }
+    }
+    if options.snapshot {
+        print!("{}", lcd.interface().snapshot(&layout));
+    }
+    Ok(())
+}
+
+/// runs a command, returns true on quit
+fn execute<const COLS: u8, const LINES: usize>(
+    lcd: &mut LiquidCrystal<'_, HD44780, COLS, LINES>,
+    delay: &mut VirtualDelay,
+    line: &str,
+) -> Result<bool, String> {
+    let (command, rest) = line
+        .trim_start()
+        .split_once(' ')
+        .unwrap_or((line.trim(), ""));
+    let args: Vec<&str> = rest.split_whitespace().collect();
+    match (command, args.as_slice()) {
+        ("text", _) => {
+            lcd.write(delay, Text(rest));
+        }
+        ("cursor", ["on"]) => {
+            lcd.enable_cursor().update_config(delay);
+        }
+        ("cursor", ["off"]) => {
+            lcd.disable_cursor().update_config(delay);
+        }
+        ("cursor", [line, column]) => {
+            let line: usize = number(line)? as usize;
+            let column = number(column)?;
+            if line >= LINES {
+                return Err(format!("the layout has {} lines", LINES));
+            }
+            if column >= COLS {
+                return Err(format!("the layout has {} columns", COLS));
+            }
+            lcd.set_cursor(delay, line, column);
+        }
+        ("blink", ["on"]) => {
+            lcd.enable_blink().update_config(delay);
+        }
+        ("blink", ["off"]) => {
+            lcd.disable_blink().update_config(delay);
+        }
+        ("display", ["on"]) => {
+            lcd.enable_display().update_config(delay);
+        }
+        ("display", ["off"]) => {
+            lcd.disable_display().update_config(delay);
+        }
+        ("autoscroll", ["on"]) => {
+            lcd.enable_autoscroll().update_config(delay);
+        }
+        ("autoscroll", ["off"]) => {
+            lcd.disable_autoscroll().update_config(delay);
+        }
+        ("scroll", ["left"]) => {
+            lcd.write(delay, Command(ShiftDisplayLeft));
+        }
+        ("scroll", ["right"]) => {
+            lcd.write(delay, Command(ShiftDisplayRight));
+        }
+        ("char", [slot, rows]) => {
+            let rows = rows
+                .split(',')
+                .map(number)
+                .collect::<Result<Vec<u8>, _>>()?;
+            let bitmap: [u8; 8] = rows
+                .try_into()
+                .map_err(|_| "a character has 8 rows".to_string())?;
+            lcd.custom_char(delay, &bitmap, slot_number(slot)?);
+        }
+        ("put", [slot]) => {
+            lcd.write(delay, CustomChar(slot_number(slot)?));
+        }
+        ("clear", []) => {
+            lcd.write(delay, Command(Clear));
+        }
+        ("home", []) => {
+            lcd.write(delay, Command(Reset));
+        }
+        ("wait", [ms]) => {
+            let ms = ms.parse().map_err(|_| format!("bad time {}", ms))?;
+            std::thread::sleep(Duration::from_millis(ms));
+        }
+        ("quit", []) => return Ok(true),
+        _ => return Err(format!("unknown command: {}", line.trim())),
+    }
+    Ok(false)
+}
+
+/// decimal, 0x hexadecimal or 0b binary
+fn number(text: &str) -> Result<u8, String> {
+    let text = text.trim();
+    let value = if let Some(hex) = text.strip_prefix("0x") {
+        u8::from_str_radix(hex, 16)
+    } else if let Some(binary) = text.strip_prefix("0b") {
+        u8::from_str_radix(binary, 2)
+    } else {
+        text.parse()
+    };
+    value.map_err(|_| format!("bad number {}", text))
+}
+
+/// custom character slot (0 - 7)
+fn slot_number(text: &str) -> Result<u8, String> {
+    match number(text)? {
+        slot @ 0..=7 => Ok(slot),
+        _ => Err(format!("bad slot {}, the slots are 0 - 7", text)),
+    }
+}
+
+fn draw<const COLS: u8, const LINES: usize>(
+    emulator: &HD44780,
+    layout: &Layout<COLS, LINES>,
+    backlight: Backlight,
+    status: &str,
+) {
+    print!(
+        "\x1b[2J\x1b[H{}{}\n> ",
+        emulator.ansi(layout, backlight),
+        status
+    );
+    let _ = io::Write::flush(&mut io::stdout());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// runs the commands on a 16x2 display, returns the display and the results
+    fn run_commands(commands: &[&str]) -> (HD44780, Vec<Result<bool, String>>) {
+        let mut emulator = HD44780::new();
+        let mut delay = VirtualDelay::default();
+        let mut lcd = LiquidCrystal::new(&mut emulator, BusBits::Bus4Bits, LCD16X2);
+        lcd.begin(&mut delay);
+        let results = commands
+            .iter()
+            .map(|command| execute(&mut lcd, &mut delay, command))
+            .collect();
+        (emulator, results)
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(number("12"), Ok(12));
+        assert_eq!(number(" 0x1f"), Ok(0x1F));
+        assert_eq!(number("0b101"), Ok(5));
+        assert_eq!(number("256"), Err("bad number 256".to_string()));
+        assert_eq!(number("0xZZ"), Err("bad number 0xZZ".to_string()));
+        assert_eq!(number("-1"), Err("bad number -1".to_string()));
+        assert_eq!(number(""), Err("bad number ".to_string()));
+    }
+
+    #[test]
+    fn writes_text() {
+        let (emulator, results) = run_commands(&["text hello", "cursor 1 2", "text World!"]);
+        assert!(results.iter().all(|result| *result == Ok(false)));
+        assert_eq!(emulator.line(0), "hello");
+        assert_eq!(emulator.line(1), "  World!");
+    }
+
+    #[test]
+    fn cursor_out_of_the_layout() {
+        let (emulator, results) = run_commands(&["cursor 1 100", "cursor 2 0", "cursor 1 16"]);
+        assert_eq!(
+            results,
+            [
+                Err("the layout has 16 columns".to_string()),
+                Err("the layout has 2 lines".to_string()),
+                Err("the layout has 16 columns".to_string()),
+            ]
+        );
+        assert_eq!(emulator.address(), 0);
+    }
+
+    #[test]
+    fn bad_arguments() {
+        let (_, results) = run_commands(&[
+            "cursor one 0",
+            "cursor maybe",
+            "wait soon",
+            "jump",
+            "clear now",
+        ]);
+        assert_eq!(
+            results,
+            [
+                Err("bad number one".to_string()),
+                Err("unknown command: cursor maybe".to_string()),
+                Err("bad time soon".to_string()),
+                Err("unknown command: jump".to_string()),
+                Err("unknown command: clear now".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_characters() {
+        let (emulator, results) = run_commands(&[
+            "char 1 0x00,0x0a,0x1f,0x1f,0x0e,0x04,0x00,0x00",
+            "put 1",
+            "char 2 1,2,3",
+            "char 2 1,2,3,4,5,6,7,8,9",
+            "char 8 0,0,0,0,0,0,0,0",
+            "char 3 0,0,0,0,0,0,0,0x100",
+            "put 9",
+        ]);
+        assert_eq!(results[..2], [Ok(false), Ok(false)]);
+        assert_eq!(
+            results[2..],
+            [
+                Err("a character has 8 rows".to_string()),
+                Err("a character has 8 rows".to_string()),
+                Err("bad slot 8, the slots are 0 - 7".to_string()),
+                Err("bad number 0x100".to_string()),
+                Err("bad slot 9, the slots are 0 - 7".to_string()),
+            ]
+        );
+        assert_eq!(
+            emulator.custom_char(1),
+            [0x00, 0x0A, 0x1F, 0x1F, 0x0E, 0x04, 0x00, 0x00]
+        );
+        assert_eq!(emulator.custom_char(2), [0; 8]);
+        assert_eq!(emulator.ddram()[0], 1);
+    }
+
+    #[test]
+    fn quits() {
+        let (_, results) = run_commands(&["home", "quit"]);
+        assert_eq!(results, [Ok(false), Ok(true)]);
+    }
+}
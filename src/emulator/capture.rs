@@ -0,0 +1,418 @@
+use std::fmt;
+
+use super::decoder::{Transaction, TransactionDecoder};
+use super::hd44780::HD44780;
+use crate::lcd_trait::{Interface, PinMap, NC, PCF8574_BACKLIGHT, PCF8574_BACKPACK};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CaptureError {
+    /// the header has no channel for this signal
+    MissingSignal(&'static str),
+    /// no header and no `PinMap`
+    MissingHeader,
+    /// line with a value that is not a number
+    BadLine(usize),
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureError::MissingSignal(signal) => write!(f, "no channel for {}", signal),
+            CaptureError::MissingHeader => write!(f, "no header, the channels must be mapped"),
+            CaptureError::BadLine(line) => write!(f, "bad value on line {}", line),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {}
+
+/// ### operations and final screen of a logic analyzer capture
+/// decodes the CSV exports of sigrok/PulseView: the levels of the parallel pins,
+/// or the bytes of the I2C decoder for the PCF8574 backpacks (`I2C` interface)
+///
+/// ```no_run
+/// # use liquid_crystal::prelude::*;
+/// # use liquid_crystal::emulator::*;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let capture = Capture::from_parallel_csv(&std::fs::read_to_string("capture.csv")?, None)?;
+/// println!("{:?}", capture.transactions);
+/// print!("{}", capture.emulator.snapshot(&LCD16X2));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Capture {
+    /// time of the falling edge (as in the file) and operation
+    pub transactions: Vec<(f64, Transaction)>,
+    /// controller on `EN` after the capture
+    pub emulator: HD44780,
+    /// last state of the backlight (I2C only)
+    pub backlight: Option<bool>,
+    decoder: TransactionDecoder,
+}
+
+impl Capture {
+    fn new() -> Capture {
+        Capture {
+            transactions: Vec::new(),
+            emulator: HD44780::new(),
+            backlight: None,
+            decoder: TransactionDecoder::new(),
+        }
+    }
+
+    fn feed(&mut self, time: f64, config: u8, data: u8) {
+        for transaction in self.decoder.decode(config, data) {
+            self.transactions.push((time, transaction));
+        }
+        self.emulator.send(config, data);
+    }
+
+    /// ### decodes the levels of the parallel pins
+    /// one line per sample, an optional time column and a column per channel.
+    /// without `pins` the channels are found by name in the header (RS, RW, E/EN, E2/EN2, D0 - D7),
+    /// with `pins` the pin numbers are the channel columns (0 is the first channel after the time)
+    pub fn from_parallel_csv(csv: &str, pins: Option<PinMap>) -> Result<Capture, CaptureError> {
+        let mut lines = csv_lines(csv).peekable();
+        let header = match lines.peek() {
+            Some((_, fields)) if fields.iter().any(|field| field.parse::<f64>().is_err()) => {
+                lines.next().map(|(_, fields)| fields)
+            }
+            _ => None,
+        };
+        let time_column = match &header {
+            Some(names) => names
+                .iter()
+                .position(|name| name.to_ascii_lowercase().starts_with("time")),
+            None => None,
+        };
+        let channels = |fields: &[&str]| -> Vec<String> {
+            fields
+                .iter()
+                .enumerate()
+                .filter(|(n, _)| Some(*n) != time_column)
+                .map(|(_, field)| field.to_string())
+                .collect()
+        };
+        let pins = match (pins, &header) {
+            (Some(pins), _) => pins,
+            (None, Some(names)) => pin_map_from_names(&channels(names))?,
+            (None, None) => return Err(CaptureError::MissingHeader),
+        };
+        let mut capture = Capture::new();
+        let mut last = None;
+        for (number, fields) in lines {
+            let time = match time_column {
+                Some(column) => parse_number(fields.get(column).copied(), number)?,
+                None => number as f64,
+            };
+            let mut word = 0u16;
+            for (pin, value) in channels(&fields).iter().enumerate().take(16) {
+                if parse_number(Some(value), number)? != 0.0 {
+                    word |= 1 << pin;
+                }
+            }
+            if last != Some(word) {
+                last = Some(word);
+                capture.feed(time, pins.unpack_config(word), pins.unpack_data(word));
+            }
+        }
+        Ok(capture)
+    }
+
+    /// ### decodes the I2C traffic of a PCF8574 backpack
+    /// reads the lines of the I2C decoder with "Address write: XX" and "Data write: XX",
+    /// the data written to `address` are the port states (`PCF8574_BACKPACK`).
+    ///
+    /// the time is the first column ("1.5e-05" or the start of a "start-end" range),
+    /// or the column named "time..." if the file has a header.
+    /// the lines without time are numbered from 1
+    pub fn from_i2c_csv(csv: &str, address: u8) -> Result<Capture, CaptureError> {
+        let mut capture = Capture::new();
+        let mut selected = false;
+        let mut time_column = 0;
+        let mut first = true;
+        for (number, line) in csv.lines().enumerate() {
+            let lower = line.to_ascii_lowercase();
+            if lower.trim().is_empty() || lower.trim_start().starts_with(';') {
+                continue;
+            }
+            if std::mem::take(&mut first) && !lower.contains("write") && !lower.contains("read") {
+                let names = lower.split(',').map(|name| name.trim().trim_matches('"'));
+                if let Some(column) = names.clone().position(|name| name.starts_with("time")) {
+                    time_column = column;
+                }
+                continue;
+            }
+            if let Some(value) = hex_after(&lower, "address write") {
+                selected = value == address;
+            } else if lower.contains("address read") {
+                selected = false;
+            } else if let Some(byte) = hex_after(&lower, "data write") {
+                if selected {
+                    let time = time_at(line, time_column).unwrap_or((number + 1) as f64);
+                    let port = byte as u16;
+                    let config = PCF8574_BACKPACK.unpack_config(port);
+                    let data = PCF8574_BACKPACK.unpack_data(port);
+                    capture.backlight = Some((byte & PCF8574_BACKLIGHT) != 0);
+                    capture.feed(time, config, data);
+                }
+            }
+        }
+        Ok(capture)
+    }
+}
+
+/// not empty and not comment lines, with their number (from 1) and fields
+fn csv_lines(csv: &str) -> impl Iterator<Item = (usize, Vec<&str>)> {
+    csv.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with(';'))
+        .map(|(number, line)| {
+            let fields = line.split(',').map(|field| field.trim().trim_matches('"'));
+            (number + 1, fields.collect())
+        })
+}
+
+fn parse_number(field: Option<&str>, line: usize) -> Result<f64, CaptureError> {
+    field
+        .and_then(|field| field.parse().ok())
+        .ok_or(CaptureError::BadLine(line))
+}
+
+/// time in the column `column` of an I2C line
+fn time_at(line: &str, column: usize) -> Option<f64> {
+    let field = line.split(',').nth(column)?.trim().trim_matches('"');
+    let field = field.split_whitespace().next()?;
+    field.parse().ok().or_else(|| {
+        // start of a "start-end" range, the '-' of the exponents is not a separator
+        let (end, _) = field
+            .char_indices()
+            .skip(1)
+            .find(|(n, c)| *c == '-' && !field[..*n].ends_with(['e', 'E']))?;
+        field[..end].parse().ok()
+    })
+}
+
+/// the hexadecimal value after `label` ("data write: 0C")
+fn hex_after(line: &str, label: &str) -> Option<u8> {
+    let start = line.find(label)? + label.len();
+    let value = line[start..]
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .find(|token| !token.is_empty())?;
+    let value = value.trim_start_matches("0x").trim_end_matches('h');
+    u8::from_str_radix(value, 16).ok()
+}
+
+/// `PinMap` of the channels named RS, RW, E/EN, E2/EN2, D0/DB0 - D7/DB7
+fn pin_map_from_names(names: &[String]) -> Result<PinMap, CaptureError> {
+    let find = |aliases: &[&str]| {
+        names
+            .iter()
+            .position(|name| {
+                let name = name.to_ascii_uppercase().replace(['/', '_', ' '], "");
+                aliases.contains(&name.as_str())
+            })
+            .map_or(NC, |pin| pin as u8)
+    };
+    let mut pins = PinMap {
+        rs: find(&["RS"]),
+        rw: find(&["RW"]),
+        en: find(&["E", "EN", "E1", "EN1"]),
+        en2: find(&["E2", "EN2"]),
+        data: [NC; 8],
+    };
+    for (n, pin) in pins.data.iter_mut().enumerate() {
+        let (d, db) = (format!("D{}", n), format!("DB{}", n));
+        *pin = find(&[d.as_str(), db.as_str()]);
+    }
+    if pins.en == NC {
+        return Err(CaptureError::MissingSignal("EN"));
+    }
+    if pins.rs == NC {
+        return Err(CaptureError::MissingSignal("RS"));
+    }
+    Ok(pins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::{Operation, VirtualClock, VirtualDelay};
+    use crate::lcd_trait::{BusBits, LiquidCrystal, SendType, EN, I2C, LCD16X2};
+    use embedded_hal::i2c::{ErrorType, I2c, Operation as I2cOperation};
+    use std::convert::Infallible;
+
+    /// wiring of the header-less capture: RS, RW, E, D4 - D7
+    const PINS: PinMap = PinMap {
+        rs: 0,
+        rw: 1,
+        en: 2,
+        en2: NC,
+        data: [NC, NC, NC, NC, 3, 4, 5, 6],
+    };
+
+    /// logic analyzer on the pins of `PINS`, one CSV line per send
+    struct Analyzer {
+        clock: VirtualClock,
+        time: bool,
+        csv: String,
+    }
+
+    impl Interface for Analyzer {
+        fn send(&mut self, config: u8, data: u8) {
+            let word = PINS.pack(config, data);
+            if self.time {
+                self.csv += &format!("{:e},", self.clock.now() as f64 / 1e9);
+            }
+            let levels: Vec<String> = (0..7).map(|pin| ((word >> pin) & 1).to_string()).collect();
+            self.csv += &levels.join(",");
+            self.csv.push('\n');
+        }
+    }
+
+    /// I2C decoder, one CSV line per address and byte
+    struct Decoder {
+        clock: VirtualClock,
+        csv: String,
+    }
+
+    impl ErrorType for Decoder {
+        type Error = Infallible;
+    }
+
+    impl I2c for Decoder {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [I2cOperation<'_>],
+        ) -> Result<(), Infallible> {
+            let time = self.clock.now() as f64 / 1e9;
+            for operation in operations {
+                if let I2cOperation::Write(bytes) = operation {
+                    self.csv += &format!("{:e},I2C: Address write: {:02X}\n", time, address);
+                    for byte in bytes.iter() {
+                        self.csv += &format!("{:e},I2C: Data write: {:02X}\n", time, byte);
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// `begin` and "hi"
+    fn drive<T: Interface>(interface: &mut T, delay: &mut VirtualDelay) {
+        let mut lcd = LiquidCrystal::new(interface, BusBits::Bus4Bits, LCD16X2);
+        lcd.begin(delay);
+        lcd.write(delay, SendType::Text("hi"));
+    }
+
+    fn parallel_csv(time: bool) -> String {
+        let clock = VirtualClock::new();
+        let mut delay = VirtualDelay::new(clock.clone());
+        let mut analyzer = Analyzer {
+            clock,
+            time,
+            csv: String::new(),
+        };
+        drive(&mut analyzer, &mut delay);
+        analyzer.csv
+    }
+
+    fn operations(capture: &Capture) -> Vec<Operation> {
+        capture
+            .transactions
+            .iter()
+            .map(|(_, transaction)| transaction.operation)
+            .collect()
+    }
+
+    /// operations of `drive` after the reset sequence
+    const DRIVE: [Operation; 7] = [
+        Operation::Command(0x28),
+        Operation::Command(0x01),
+        Operation::Command(0x02),
+        Operation::Command(0x0C),
+        Operation::Command(0x06),
+        Operation::Data(b'h'),
+        Operation::Data(b'i'),
+    ];
+
+    #[test]
+    fn parallel_with_header() {
+        let csv = format!("Time [s],RS,RW,E,D4,D5,D6,D7\n{}", parallel_csv(true));
+        let capture = Capture::from_parallel_csv(&csv, None).unwrap();
+
+        assert!(operations(&capture).ends_with(&DRIVE));
+        assert_eq!(capture.emulator.line(0), "hi");
+        assert_eq!(capture.backlight, None);
+        let (time, transaction) = capture.transactions.last().unwrap();
+        assert_eq!(transaction.enable, EN);
+        assert!(*time > 0.05 && *time < 0.06, "time {}", time);
+    }
+
+    #[test]
+    fn parallel_with_pin_map() {
+        let capture = Capture::from_parallel_csv(&parallel_csv(false), Some(PINS)).unwrap();
+
+        assert!(operations(&capture).ends_with(&DRIVE));
+        assert_eq!(capture.emulator.line(0), "hi");
+        // numbered lines
+        assert_eq!(capture.transactions[0].0, 3.0);
+    }
+
+    #[test]
+    fn parallel_errors() {
+        let csv = parallel_csv(false);
+        assert!(matches!(
+            Capture::from_parallel_csv(&csv, None),
+            Err(CaptureError::MissingHeader)
+        ));
+        assert!(matches!(
+            Capture::from_parallel_csv("time,RS,RW,D4\n0,0,0,0\n", None),
+            Err(CaptureError::MissingSignal("EN"))
+        ));
+        assert!(matches!(
+            Capture::from_parallel_csv("time,E,RW,D4\n0,0,0,0\n", None),
+            Err(CaptureError::MissingSignal("RS"))
+        ));
+        assert!(matches!(
+            Capture::from_parallel_csv("time,RS,E\n0,0,0\n1e-6,0,x\n", None),
+            Err(CaptureError::BadLine(3))
+        ));
+    }
+
+    #[test]
+    fn i2c_backpack() {
+        let clock = VirtualClock::new();
+        let mut delay = VirtualDelay::new(clock.clone());
+        let mut decoder = Decoder {
+            clock,
+            csv: String::new(),
+        };
+        drive(&mut I2C::new(&mut decoder, 0x27), &mut delay);
+        let mut csv = String::from("Time,Annotation\n");
+        // traffic of another device and a read, not sent to the display
+        csv += "1e-7,I2C: Address write: 20\n1.5e-7,I2C: Data write: 00\n";
+        csv += "2e-7,I2C: Address read: 27\n2.5e-7,I2C: Data write: 00\n";
+        csv += &decoder.csv;
+        // '!' (0x21) without time
+        csv += "I2C: Address write: 27\nData write: 2D\nData write: 29\n";
+        csv += "Data write: 1D\nData write: 19\n";
+
+        let capture = Capture::from_i2c_csv(&csv, 0x27).unwrap();
+        assert!(operations(&capture)[..operations(&capture).len() - 1].ends_with(&DRIVE));
+        assert_eq!(capture.emulator.line(0), "hi!");
+        assert_eq!(capture.backlight, Some(true));
+        assert!(capture.transactions[0].0 > 0.04);
+        let (time, transaction) = capture.transactions.last().unwrap();
+        assert_eq!(transaction.operation, Operation::Data(b'!'));
+        assert_eq!(*time, csv.lines().count() as f64);
+        // times with negative exponents
+        let (time, _) = capture
+            .transactions
+            .iter()
+            .find(|(_, transaction)| transaction.operation == Operation::Command(0x01))
+            .unwrap();
+        assert!(*time > 0.05 && *time < 0.06, "time {}", time);
+    }
+}
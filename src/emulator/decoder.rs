@@ -0,0 +1,167 @@
+use crate::lcd_trait::{_RW, EN, RS};
+
+/// ### a complete transfer on the bus of one controller
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    /// instruction written (RS = 0)
+    Command(u8),
+    /// data written to the DDRAM/CGRAM (RS = 1)
+    Data(u8),
+    /// read of the busy flag and address (RS = 0) or of the DDRAM/CGRAM (RS = 1)
+    Read { rs: bool },
+}
+
+/// ### decodes the `config`/`data` pairs of `Interface::send`
+/// watches the falling edges of one enable and joins the nibbles in 4 bits mode.
+/// starts in 8 bits (like after power on) and follows the function set (DL bit)
+pub struct BusDecoder {
+    enable: u8,
+    eight_bits: bool,
+    nibble: Option<u8>,
+    last_config: u8,
+}
+
+impl BusDecoder {
+    /// `enable` is the bit of the enable in `config` (`EN` or `EN << 1`)
+    pub fn new(enable: u8) -> BusDecoder {
+        BusDecoder {
+            enable,
+            eight_bits: true,
+            nibble: None,
+            last_config: 0,
+        }
+    }
+
+    /// ### true in 8 bits mode
+    pub fn eight_bits(&self) -> bool {
+        self.eight_bits
+    }
+
+    /// ### true if the first nibble of a 4 bits transfer was received
+    pub fn nibble_pending(&self) -> bool {
+        self.nibble.is_some()
+    }
+
+    /// ### the bit of the watched enable
+    pub fn enable(&self) -> u8 {
+        self.enable
+    }
+
+    /// ### true if the enable is high in `config`
+    pub fn enable_high(&self, config: u8) -> bool {
+        (config & self.enable) != 0
+    }
+
+    /// ### decodes a `send`
+    /// returns the operation completed by the falling edge of the enable
+    pub fn decode(&mut self, config: u8, data: u8) -> Option<Operation> {
+        let falling = self.enable_high(self.last_config) && !self.enable_high(config);
+        self.last_config = config;
+        if !falling {
+            return None;
+        }
+        let byte = if self.eight_bits {
+            data
+        } else if let Some(high) = self.nibble.take() {
+            high | (data >> 4)
+        } else {
+            self.nibble = Some(data & 0xF0);
+            return None;
+        };
+        let rs = (config & RS) != 0;
+        if (config & _RW) != 0 {
+            return Some(Operation::Read { rs });
+        }
+        if rs {
+            Some(Operation::Data(byte))
+        } else {
+            if (byte & 0xE0) == 0x20 {
+                self.eight_bits = (byte & 0x10) != 0;
+            }
+            Some(Operation::Command(byte))
+        }
+    }
+}
+
+impl Default for BusDecoder {
+    fn default() -> Self {
+        Self::new(EN)
+    }
+}
+
+/// ### an operation and the enables that received it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Transaction {
+    /// `EN`, `EN << 1` or both
+    pub enable: u8,
+    pub operation: Operation,
+}
+
+impl Transaction {
+    /// ### `config`/`data` pair of the transaction
+    /// RS, RW and the enables with the bits of `Interface::send`
+    pub fn to_pair(self) -> (u8, u8) {
+        match self.operation {
+            Operation::Command(command) => (self.enable, command),
+            Operation::Data(data) => (self.enable | RS, data),
+            Operation::Read { rs } => (self.enable | _RW | (rs as u8), 0),
+        }
+    }
+
+    /// ### transaction of a `config`/`data` pair (see `to_pair`)
+    pub fn from_pair(config: u8, data: u8) -> Transaction {
+        let operation = match (config & _RW != 0, config & RS != 0) {
+            (true, rs) => Operation::Read { rs },
+            (false, true) => Operation::Data(data),
+            (false, false) => Operation::Command(data),
+        };
+        Transaction {
+            enable: config & (EN | (EN << 1)),
+            operation,
+        }
+    }
+}
+
+/// ### decodes the transactions of both enables
+/// an operation received by both enables at the same time is a single transaction
+pub struct TransactionDecoder {
+    decoders: [BusDecoder; 2],
+}
+
+impl TransactionDecoder {
+    pub fn new() -> TransactionDecoder {
+        TransactionDecoder {
+            decoders: [BusDecoder::new(EN), BusDecoder::new(EN << 1)],
+        }
+    }
+
+    /// ### the decoders of `EN` and `EN << 1`
+    pub fn decoders(&self) -> &[BusDecoder; 2] {
+        &self.decoders
+    }
+
+    /// ### decodes a `send`
+    /// returns the transactions completed by this `send`
+    pub fn decode(&mut self, config: u8, data: u8) -> Vec<Transaction> {
+        let first = self.decoders[0].decode(config, data);
+        let second = self.decoders[1].decode(config, data);
+        match (first, second) {
+            (Some(first), Some(second)) if first == second => vec![Transaction {
+                enable: EN | (EN << 1),
+                operation: first,
+            }],
+            _ => [(EN, first), (EN << 1, second)]
+                .into_iter()
+                .filter_map(|(enable, operation)| {
+                    operation.map(|operation| Transaction { enable, operation })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Default for TransactionDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,428 @@
+use super::decoder::{BusDecoder, Operation};
+use crate::lcd_trait::{Interface, ReadInterface, _RW, EN, RS};
+
+/// ### software HD44780
+/// implements `Interface` (and `ReadInterface`), executes the instructions like the controller:
+/// DDRAM, CGRAM, address counter, entry mode, display control and display shift.
+/// the instructions take no time, the busy flag is always 0
+///
+/// ```
+/// # use liquid_crystal::prelude::*;
+/// # use liquid_crystal::emulator::*;
+/// # let mut delay = VirtualDelay::default();
+/// let mut emulator = HD44780::new();
+/// let mut lcd = LiquidCrystal::new(&mut emulator, Bus4Bits, LCD16X2);
+/// lcd.begin(&mut delay);
+/// lcd.set_cursor(&mut delay, 1, 0).write(&mut delay, Text("hello"));
+/// assert_eq!(lcd.interface().line(1), "hello");
+/// ```
+pub struct HD44780 {
+    decoder: BusDecoder,
+    ddram: [u8; 128],
+    cgram: [u8; 64],
+    address: u8,
+    cgram_selected: bool,
+    increment: bool,
+    entry_shift: bool,
+    display: bool,
+    cursor: bool,
+    blink: bool,
+    shift: u8,
+    two_lines: bool,
+    font_5x10: bool,
+    config: u8,
+}
+
+impl HD44780 {
+    /// ### controller on `EN`, in the state of the internal reset
+    pub fn new() -> HD44780 {
+        HD44780::on_enable(EN)
+    }
+
+    /// ### controller on the enable `enable` (`EN` or `EN << 1` for the second controller)
+    pub fn on_enable(enable: u8) -> HD44780 {
+        HD44780 {
+            decoder: BusDecoder::new(enable),
+            ddram: [b' '; 128],
+            cgram: [0; 64],
+            address: 0,
+            cgram_selected: false,
+            increment: true,
+            entry_shift: false,
+            display: false,
+            cursor: false,
+            blink: false,
+            shift: 0,
+            two_lines: false,
+            font_5x10: false,
+            config: 0,
+        }
+    }
+
+    /// ### executes an instruction
+    pub fn command(&mut self, command: u8) {
+        match command {
+            0x80..=0xFF => {
+                self.address = command & 0x7F;
+                self.cgram_selected = false;
+            }
+            0x40..=0x7F => {
+                self.address = command & 0x3F;
+                self.cgram_selected = true;
+            }
+            0x20..=0x3F => {
+                self.two_lines = (command & 0x08) != 0;
+                self.font_5x10 = (command & 0x04) != 0;
+            }
+            0x10..=0x1F => {
+                let right = (command & 0x04) != 0;
+                if (command & 0x08) != 0 {
+                    self.shift_display(right);
+                } else {
+                    self.address = self.next_address(right);
+                }
+            }
+            0x08..=0x0F => {
+                self.display = (command & 0x04) != 0;
+                self.cursor = (command & 0x02) != 0;
+                self.blink = (command & 0x01) != 0;
+            }
+            0x04..=0x07 => {
+                self.increment = (command & 0x02) != 0;
+                self.entry_shift = (command & 0x01) != 0;
+            }
+            0x02..=0x03 => {
+                self.address = 0;
+                self.cgram_selected = false;
+                self.shift = 0;
+            }
+            0x01 => {
+                self.ddram = [b' '; 128];
+                self.address = 0;
+                self.cgram_selected = false;
+                self.increment = true;
+                self.shift = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// ### writes a byte in the DDRAM/CGRAM
+    pub fn write_data(&mut self, data: u8) {
+        if self.cgram_selected {
+            self.cgram[self.address as usize] = data;
+        } else {
+            self.ddram[self.address as usize] = data;
+            if self.entry_shift {
+                self.shift_display(!self.increment);
+            }
+        }
+        self.address = self.next_address(self.increment);
+    }
+
+    /// ### reads a byte of the DDRAM/CGRAM
+    pub fn read_data(&mut self) -> u8 {
+        let data = self.ram_at_address();
+        self.address = self.next_address(self.increment);
+        data
+    }
+
+    /// ### text of a line of the DDRAM
+    /// (0x00 - 0x27 and 0x40 - 0x67 in 2 lines mode, 0x00 - 0x4F in 1 line mode),
+    /// without the spaces at the end.
+    ///
+    /// `line` is 0 or 1 (only 0 in 1 line mode), the other lines are empty.
+    /// the lines 3 and 4 of the 4 lines displays are the end of the lines 1 and 2,
+    /// use `visible_lines` to get the lines of a layout
+    pub fn line(&self, line: u8) -> String {
+        let (start, len) = match (self.two_lines, line) {
+            (true, 0 | 1) => (line * 0x40, 40),
+            (false, 0) => (0, 80),
+            _ => return String::new(),
+        };
+        let text = self.text_at(start, len);
+        text.trim_end_matches(' ').to_string()
+    }
+
+    /// ### text of `len` bytes of the DDRAM from `address`
+    /// the bytes are converted as Latin-1, the custom characters are '\0' - '\x07'.
+    /// the bit 7 of `address` is ignored (0x80 | address of the set DDRAM address instruction)
+    pub fn text_at(&self, address: u8, len: usize) -> String {
+        self.ddram[(address & 0x7F) as usize..]
+            .iter()
+            .take(len)
+            .map(|byte| char::from(*byte))
+            .collect()
+    }
+
+    /// ### DDRAM indexed by address
+    pub fn ddram(&self) -> &[u8; 128] {
+        &self.ddram
+    }
+
+    /// ### CGRAM, 8 bytes per custom character
+    pub fn cgram(&self) -> &[u8; 64] {
+        &self.cgram
+    }
+
+    /// ### bitmap of a custom character
+    pub fn custom_char(&self, slot: u8) -> [u8; 8] {
+        let start = (slot as usize & 7) * 8;
+        let mut bitmap = [0; 8];
+        bitmap.copy_from_slice(&self.cgram[start..start + 8]);
+        bitmap
+    }
+
+    /// ### address counter
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// ### true if the address counter points to the CGRAM
+    pub fn cgram_selected(&self) -> bool {
+        self.cgram_selected
+    }
+
+    /// ### display shift, in characters to the left (0 - 39 in 2 lines mode, 0 - 79 in 1 line mode)
+    pub fn shift(&self) -> u8 {
+        self.shift
+    }
+
+    pub fn display_on(&self) -> bool {
+        self.display
+    }
+
+    pub fn cursor_on(&self) -> bool {
+        self.cursor
+    }
+
+    pub fn blink_on(&self) -> bool {
+        self.blink
+    }
+
+    /// ### entry mode: true if the address is incremented
+    pub fn increment(&self) -> bool {
+        self.increment
+    }
+
+    /// ### entry mode: true if the display shifts on each write
+    pub fn entry_shift(&self) -> bool {
+        self.entry_shift
+    }
+
+    pub fn two_lines(&self) -> bool {
+        self.two_lines
+    }
+
+    pub fn font_5x10(&self) -> bool {
+        self.font_5x10
+    }
+
+    /// ### true in 8 bits mode
+    pub fn eight_bits(&self) -> bool {
+        self.decoder.eight_bits()
+    }
+
+    fn ram_at_address(&self) -> u8 {
+        if self.cgram_selected {
+            self.cgram[self.address as usize]
+        } else {
+            self.ddram[self.address as usize]
+        }
+    }
+
+    fn next_address(&self, increment: bool) -> u8 {
+        let address = self.address;
+        if self.cgram_selected {
+            return if increment {
+                address + 1
+            } else {
+                address.wrapping_sub(1)
+            } & 0x3F;
+        }
+        match (self.two_lines, increment) {
+            (true, true) if address == 0x27 => 0x40,
+            (true, true) if address >= 0x67 => 0x00,
+            (true, false) if address == 0x00 => 0x67,
+            (true, false) if address == 0x40 => 0x27,
+            (false, true) if address >= 0x4F => 0x00,
+            (false, false) if address == 0x00 => 0x4F,
+            (_, true) => address + 1,
+            (_, false) => address - 1,
+        }
+    }
+
+    /// `right` moves the text to the right
+    fn shift_display(&mut self, right: bool) {
+        let len = if self.two_lines { 40 } else { 80 };
+        self.shift = if right {
+            (self.shift + len - 1) % len
+        } else {
+            (self.shift + 1) % len
+        };
+    }
+}
+
+impl Default for HD44780 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interface for HD44780 {
+    fn send(&mut self, config: u8, data: u8) {
+        self.config = config;
+        match self.decoder.decode(config, data) {
+            Some(Operation::Command(command)) => self.command(command),
+            Some(Operation::Data(data)) => self.write_data(data),
+            Some(Operation::Read { rs: true }) => {
+                self.address = self.next_address(self.increment);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ReadInterface for HD44780 {
+    fn read(&mut self) -> u8 {
+        if (self.config & _RW) == 0 || !self.decoder.enable_high(self.config) {
+            return 0;
+        }
+        let byte = if (self.config & RS) != 0 {
+            self.ram_at_address()
+        } else {
+            self.address
+        };
+        if self.decoder.eight_bits() {
+            byte
+        } else if self.decoder.nibble_pending() {
+            byte << 4
+        } else {
+            byte & 0xF0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::VirtualDelay;
+    use crate::lcd_trait::{BusBits, LiquidCrystal, SendType, ShiftDisplayRight, LCD16X2, LCD20X4};
+
+    fn buses() -> [BusBits; 2] {
+        [BusBits::Bus4Bits, BusBits::Bus8Bits]
+    }
+
+    #[test]
+    fn writes_both_lines() {
+        for bus in buses() {
+            let eight_bits = matches!(bus, BusBits::Bus8Bits);
+            let mut delay = VirtualDelay::default();
+            let mut emulator = HD44780::new();
+            let mut lcd = LiquidCrystal::new(&mut emulator, bus, LCD16X2);
+            lcd.begin(&mut delay);
+            lcd.write(&mut delay, SendType::Text("hello"));
+            lcd.set_cursor(&mut delay, 1, 3)
+                .write(&mut delay, SendType::Text("World!"));
+
+            assert_eq!(emulator.line(0), "hello");
+            assert_eq!(emulator.line(1), "   World!");
+            assert_eq!(emulator.eight_bits(), eight_bits);
+            assert!(emulator.two_lines());
+            assert!(emulator.display_on());
+            assert_eq!(emulator.address(), 0x49);
+        }
+    }
+
+    #[test]
+    fn lines_3_and_4_continue_lines_1_and_2() {
+        let mut delay = VirtualDelay::default();
+        let mut emulator = HD44780::new();
+        let mut lcd = LiquidCrystal::new(&mut emulator, BusBits::Bus4Bits, LCD20X4);
+        lcd.begin(&mut delay);
+        lcd.set_cursor(&mut delay, 2, 0)
+            .write(&mut delay, SendType::Text("third"));
+
+        assert_eq!(emulator.line(0), format!("{}third", " ".repeat(20)));
+        assert_eq!(emulator.line(2), "");
+        assert_eq!(emulator.visible_lines(&LCD20X4)[2].trim_end(), "third");
+    }
+
+    #[test]
+    fn text_at_ignores_bit_7() {
+        let mut emulator = HD44780::new();
+        emulator.command(0xC0);
+        emulator.write_data(b'a');
+
+        assert_eq!(emulator.text_at(0xC0, 1), "a");
+        assert_eq!(emulator.text_at(0x40, 1), "a");
+        assert_eq!(emulator.text_at(0xFF, 5), " ");
+    }
+
+    #[test]
+    fn custom_char_in_cgram() {
+        let bitmap = [0x00, 0x0A, 0x1F, 0x1F, 0x0E, 0x04, 0x00, 0x00];
+        for bus in buses() {
+            let mut delay = VirtualDelay::default();
+            let mut emulator = HD44780::new();
+            let mut lcd = LiquidCrystal::new(&mut emulator, bus, LCD16X2);
+            lcd.begin(&mut delay);
+            lcd.custom_char(&mut delay, &bitmap, 2);
+            lcd.write(&mut delay, SendType::CustomChar(2));
+
+            assert_eq!(emulator.custom_char(2), bitmap);
+            assert_eq!(&emulator.cgram()[16..24], &bitmap);
+            assert_eq!(emulator.cgram()[..16], [0; 16]);
+            assert!(!emulator.cgram_selected());
+            assert_eq!(emulator.ddram()[0], 2);
+        }
+    }
+
+    #[test]
+    fn entry_mode_and_display_shift() {
+        for bus in buses() {
+            let mut delay = VirtualDelay::default();
+            let mut emulator = HD44780::new();
+            let mut lcd = LiquidCrystal::new(&mut emulator, bus, LCD16X2);
+            lcd.begin(&mut delay);
+            lcd.enable_autoscroll()
+                .set_autoscroll_increment()
+                .update_config(&mut delay);
+            lcd.write(&mut delay, SendType::Text("ab"));
+            assert!(lcd.interface().entry_shift());
+            assert!(lcd.interface().increment());
+            assert_eq!(lcd.interface().shift(), 2);
+
+            lcd.write(&mut delay, SendType::Command(ShiftDisplayRight));
+            assert_eq!(lcd.interface().shift(), 1);
+
+            lcd.disable_autoscroll()
+                .set_autoscroll_decrement()
+                .update_config(&mut delay);
+            lcd.write(&mut delay, SendType::Text("c"));
+            assert!(!lcd.interface().increment());
+            assert_eq!(lcd.interface().shift(), 1);
+            assert_eq!(lcd.interface().line(0), "abc");
+            assert_eq!(lcd.interface().address(), 0x01);
+        }
+    }
+
+    #[test]
+    fn reads_back_ram_and_address() {
+        for bus in buses() {
+            let mut delay = VirtualDelay::default();
+            let mut emulator = HD44780::new();
+            let mut lcd = LiquidCrystal::new(&mut emulator, bus, LCD16X2);
+            lcd.begin(&mut delay);
+            lcd.set_cursor(&mut delay, 1, 0)
+                .write(&mut delay, SendType::Text("hi"));
+            assert_eq!(lcd.read_address(&mut delay), 0x42);
+            assert!(!lcd.read_busy_flag(&mut delay));
+
+            lcd.set_cursor(&mut delay, 1, 0);
+            assert_eq!(lcd.read_data(&mut delay), b'h');
+            assert_eq!(lcd.read_data(&mut delay), b'i');
+            assert_eq!(lcd.read_address(&mut delay), 0x42);
+        }
+    }
+}
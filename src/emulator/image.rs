@@ -0,0 +1,405 @@
+use std::fmt::Write;
+
+use super::hd44780::HD44780;
+use super::rom::Rom;
+use crate::lcd_trait::Layout;
+
+/// ### look of the exported images
+#[derive(Clone, Copy, Debug)]
+pub struct ImageStyle {
+    pub rom: Rom,
+    /// size of a dot in pixels
+    pub dot: u32,
+    /// space between the dots in pixels
+    pub dot_gap: u32,
+    /// space between the characters in pixels
+    pub cell_gap: u32,
+    /// space around the characters in pixels
+    pub margin: u32,
+    /// color of the dots on
+    pub on: [u8; 3],
+    /// color of the dots off
+    pub off: [u8; 3],
+    /// color around the dots
+    pub background: [u8; 3],
+}
+
+/// dark dots on yellow green
+pub const GREEN_LCD: ImageStyle = ImageStyle {
+    rom: Rom::A00,
+    dot: 3,
+    dot_gap: 1,
+    cell_gap: 4,
+    margin: 12,
+    on: [0x1E, 0x2A, 0x08],
+    off: [0x8F, 0xB0, 0x1C],
+    background: [0x9C, 0xBE, 0x20],
+};
+
+/// white dots on blue
+pub const BLUE_LCD: ImageStyle = ImageStyle {
+    rom: Rom::A00,
+    dot: 3,
+    dot_gap: 1,
+    cell_gap: 4,
+    margin: 12,
+    on: [0xF0, 0xF4, 0xFF],
+    off: [0x1C, 0x34, 0xD8],
+    background: [0x12, 0x28, 0xC8],
+};
+
+/// state of a pixel of the image
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Pixel {
+    Background,
+    Off,
+    On,
+}
+
+impl HD44780 {
+    /// ### glyph of `code` in the current font
+    /// the CGRAM for 0x00 - 0x0F, else the ROM. 8 rows in 5x8 and 11 rows in 5x10,
+    /// the last row is the cursor
+    pub fn glyph(&self, rom: Rom, code: u8) -> [u8; 11] {
+        let mut glyph = [0; 11];
+        match (code, self.font_5x10()) {
+            (0x00..=0x0F, false) => glyph[..8].copy_from_slice(&self.custom_char(code)),
+            (0x00..=0x0F, true) => {
+                let start = ((code as usize >> 1) & 3) * 16;
+                glyph.copy_from_slice(&self.cgram()[start..start + 11]);
+            }
+            (_, false) => glyph[..8].copy_from_slice(&rom.glyph_5x8(code)),
+            (_, true) => glyph = rom.glyph_5x10(code),
+        }
+        glyph
+    }
+
+    /// ### dots of the visible window
+    /// one `Vec` per row of dots, 5 dots per character, with the cursor (blink in the on phase)
+    pub fn dots<const COLS: u8, const LINES: usize>(
+        &self,
+        layout: &Layout<COLS, LINES>,
+        rom: Rom,
+    ) -> Vec<Vec<bool>> {
+        let rows = self.glyph_rows();
+        let cursor = if self.display_on() {
+            self.cursor_position(layout)
+        } else {
+            None
+        };
+        let mut dots = Vec::new();
+        for (line, bytes) in self.visible_bytes(layout).iter().enumerate() {
+            let glyphs: Vec<[u8; 11]> = bytes
+                .iter()
+                .enumerate()
+                .map(|(column, byte)| {
+                    let mut glyph = if self.display_on() {
+                        self.glyph(rom, *byte)
+                    } else {
+                        [0; 11]
+                    };
+                    if cursor == Some((line, column as u8)) {
+                        if self.cursor_on() {
+                            glyph[rows - 1] = 0x1F;
+                        }
+                        if self.blink_on() {
+                            glyph = [0x1F; 11];
+                        }
+                    }
+                    glyph
+                })
+                .collect();
+            for row in 0..rows {
+                dots.push(
+                    glyphs
+                        .iter()
+                        .flat_map(|glyph| (0..5).map(move |x| (glyph[row] & (0x10 >> x)) != 0))
+                        .collect(),
+                );
+            }
+        }
+        dots
+    }
+
+    /// ### binary PBM (P4) image of the visible window
+    /// black for the dots on, white for the rest
+    pub fn pbm<const COLS: u8, const LINES: usize>(
+        &self,
+        layout: &Layout<COLS, LINES>,
+        style: &ImageStyle,
+    ) -> Vec<u8> {
+        let (width, height, pixels) = self.raster(layout, style);
+        let mut image = format!("P4\n{} {}\n", width, height).into_bytes();
+        for row in pixels.chunks(width as usize) {
+            for byte in row.chunks(8) {
+                let bits = byte
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, pixel)| **pixel == Pixel::On)
+                    .fold(0u8, |bits, (n, _)| bits | (0x80 >> n));
+                image.push(bits);
+            }
+        }
+        image
+    }
+
+    /// ### binary PPM (P6) image of the visible window
+    pub fn ppm<const COLS: u8, const LINES: usize>(
+        &self,
+        layout: &Layout<COLS, LINES>,
+        style: &ImageStyle,
+    ) -> Vec<u8> {
+        let (width, height, pixels) = self.raster(layout, style);
+        let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        for pixel in pixels {
+            image.extend_from_slice(&style.color(pixel));
+        }
+        image
+    }
+
+    /// ### SVG image of the visible window, a rectangle per dot
+    pub fn svg<const COLS: u8, const LINES: usize>(
+        &self,
+        layout: &Layout<COLS, LINES>,
+        style: &ImageStyle,
+    ) -> String {
+        let dots = self.dots(layout, style.rom);
+        let (width, height) = style.size(COLS as u32, LINES as u32, self.glyph_rows() as u32);
+        let mut image = String::new();
+        let _ = writeln!(
+            image,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            width, height, width, height
+        );
+        let _ = writeln!(
+            image,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            width,
+            height,
+            hex(style.background)
+        );
+        for (y, row) in dots.iter().enumerate() {
+            for (x, dot) in row.iter().enumerate() {
+                let (left, top) = style.dot_position(x as u32, y as u32, self.glyph_rows() as u32);
+                let color = if *dot { style.on } else { style.off };
+                let _ = writeln!(
+                    image,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    left,
+                    top,
+                    style.dot,
+                    style.dot,
+                    hex(color)
+                );
+            }
+        }
+        image.push_str("</svg>\n");
+        image
+    }
+
+    /// rows of a character with the cursor
+    fn glyph_rows(&self) -> usize {
+        if self.font_5x10() {
+            11
+        } else {
+            8
+        }
+    }
+
+    /// width, height and pixels (row by row) of the image
+    fn raster<const COLS: u8, const LINES: usize>(
+        &self,
+        layout: &Layout<COLS, LINES>,
+        style: &ImageStyle,
+    ) -> (u32, u32, Vec<Pixel>) {
+        let rows = self.glyph_rows() as u32;
+        let (width, height) = style.size(COLS as u32, LINES as u32, rows);
+        let mut pixels = vec![Pixel::Background; (width * height) as usize];
+        for (y, row) in self.dots(layout, style.rom).iter().enumerate() {
+            for (x, dot) in row.iter().enumerate() {
+                let (left, top) = style.dot_position(x as u32, y as u32, rows);
+                let pixel = if *dot { Pixel::On } else { Pixel::Off };
+                for py in top..top + style.dot {
+                    let start = (py * width + left) as usize;
+                    pixels[start..start + style.dot as usize].fill(pixel);
+                }
+            }
+        }
+        (width, height, pixels)
+    }
+}
+
+impl ImageStyle {
+    fn pitch(&self) -> u32 {
+        self.dot + self.dot_gap
+    }
+
+    /// size in pixels of the image
+    fn size(&self, cols: u32, lines: u32, rows: u32) -> (u32, u32) {
+        let cell_width = 5 * self.pitch() - self.dot_gap;
+        let cell_height = rows * self.pitch() - self.dot_gap;
+        (
+            2 * self.margin + cols * cell_width + cols.saturating_sub(1) * self.cell_gap,
+            2 * self.margin + lines * cell_height + lines.saturating_sub(1) * self.cell_gap,
+        )
+    }
+
+    /// top left corner of a dot
+    fn dot_position(&self, x: u32, y: u32, rows: u32) -> (u32, u32) {
+        let cell_width = 5 * self.pitch() - self.dot_gap + self.cell_gap;
+        let cell_height = rows * self.pitch() - self.dot_gap + self.cell_gap;
+        (
+            self.margin + (x / 5) * cell_width + (x % 5) * self.pitch(),
+            self.margin + (y / rows) * cell_height + (y % rows) * self.pitch(),
+        )
+    }
+
+    fn color(&self, pixel: Pixel) -> [u8; 3] {
+        match pixel {
+            Pixel::Background => self.background,
+            Pixel::Off => self.off,
+            Pixel::On => self.on,
+        }
+    }
+}
+
+fn hex(color: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcd_trait::LCD16X2;
+
+    const STYLE: ImageStyle = ImageStyle {
+        rom: Rom::A00,
+        dot: 2,
+        dot_gap: 1,
+        cell_gap: 3,
+        margin: 5,
+        on: [0xFF, 0xFF, 0xFF],
+        off: [0x80, 0x80, 0x80],
+        background: [0x00, 0x00, 0x00],
+    };
+
+    /// one character without margins, 5 x 8 (or 5 x 11) pixels
+    const PIXELS: ImageStyle = ImageStyle {
+        dot: 1,
+        dot_gap: 0,
+        cell_gap: 0,
+        margin: 0,
+        ..STYLE
+    };
+
+    const ONE_CHAR: Layout<1, 1> = Layout { addrs: [0x80] };
+
+    /// display on, 2 lines or 1 line in 5x10
+    fn emulator(font_5x10: bool) -> HD44780 {
+        let mut emulator = HD44780::new();
+        emulator.command(if font_5x10 { 0x24 } else { 0x28 });
+        emulator.command(0x0C);
+        emulator
+    }
+
+    /// bytes after the header of `lines` lines
+    fn body(image: &[u8], lines: usize) -> &[u8] {
+        let start = image
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| **byte == b'\n')
+            .nth(lines - 1)
+            .unwrap()
+            .0;
+        &image[start + 1..]
+    }
+
+    #[test]
+    fn image_size() {
+        // 5 x 3 - 1 = 14 pixels per character, 8 x 3 - 1 = 23 rows per line
+        let (width, height) = (5 + 16 * 14 + 15 * 3 + 5, 5 + 2 * 23 + 3 + 5);
+        let emulator = emulator(false);
+
+        let ppm = emulator.ppm(&LCD16X2, &STYLE);
+        assert!(ppm.starts_with(format!("P6\n{} {}\n255\n", width, height).as_bytes()));
+        assert_eq!(body(&ppm, 3).len(), width * height * 3);
+        assert_eq!(&body(&ppm, 3)[..3], &STYLE.background);
+        let first_dot = (5 * width + 5) * 3;
+        assert_eq!(&body(&ppm, 3)[first_dot..first_dot + 3], &STYLE.off);
+
+        let pbm = emulator.pbm(&LCD16X2, &STYLE);
+        assert!(pbm.starts_with(format!("P4\n{} {}\n", width, height).as_bytes()));
+        assert_eq!(width % 8, 7);
+        assert_eq!(body(&pbm, 2).len(), width.div_ceil(8) * height);
+
+        let svg = emulator.svg(&LCD16X2, &STYLE);
+        assert!(svg.contains(&format!(r#"width="{}" height="{}""#, width, height)));
+        assert_eq!(svg.matches("<rect").count(), 1 + 16 * 5 * 2 * 8);
+        assert!(svg.contains(r##"<rect x="5" y="5" width="2" height="2" fill="#808080"/>"##));
+    }
+
+    #[test]
+    fn pbm_rows_padded_to_bytes() {
+        let mut emulator = emulator(false);
+        emulator.command(0x0D); // blinking cursor on the character
+        let pbm = emulator.pbm(&ONE_CHAR, &PIXELS);
+        assert!(pbm.starts_with(b"P4\n5 8\n"));
+        assert_eq!(body(&pbm, 2), &[0xF8; 8]);
+    }
+
+    #[test]
+    fn rom_glyphs() {
+        let mut emulator = emulator(false);
+        emulator.write_data(0x5C);
+        assert_eq!(
+            emulator.glyph(Rom::A00, 0x41)[..8],
+            [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x00]
+        );
+        // yen on the A00, backslash on the A02
+        assert_eq!(
+            emulator.glyph(Rom::A00, 0x5C)[..8],
+            [0x11, 0x0A, 0x1F, 0x04, 0x1F, 0x04, 0x04, 0x00]
+        );
+        assert_eq!(
+            emulator.glyph(Rom::A02, 0x5C)[..8],
+            [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00, 0x00]
+        );
+        let dots = emulator.dots(&ONE_CHAR, Rom::A02);
+        assert_eq!(dots.len(), 8);
+        assert_eq!(dots[1], [true, false, false, false, false]);
+        assert_eq!(dots[5], [false, false, false, false, true]);
+    }
+
+    #[test]
+    fn custom_glyphs_5x10() {
+        let mut emulator = emulator(true);
+        let bitmap = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+        emulator.command(0x40 | 16);
+        bitmap.iter().for_each(|row| emulator.write_data(*row));
+
+        // codes 2, 3, 10 and 11 are the second character of the CGRAM
+        for code in [0x02, 0x03, 0x0A, 0x0B] {
+            assert_eq!(emulator.glyph(Rom::A00, code), bitmap);
+        }
+        assert_eq!(emulator.glyph(Rom::A00, 0x00), [0; 11]);
+        assert_eq!(
+            emulator.glyph(Rom::A00, 0xE7),
+            [0x00, 0x00, 0x0F, 0x11, 0x11, 0x11, 0x0F, 0x01, 0x01, 0x0E, 0x00]
+        );
+    }
+
+    #[test]
+    fn cursor_row() {
+        for (font_5x10, rows) in [(false, 8), (true, 11)] {
+            let mut emulator = emulator(font_5x10);
+            emulator.command(0x0E); // underline cursor
+            let dots = emulator.dots(&ONE_CHAR, Rom::A00);
+            assert_eq!(dots.len(), rows);
+            assert_eq!(dots[rows - 1], [true; 5]);
+            assert!(dots[..rows - 1].iter().flatten().all(|dot| !dot));
+
+            let pbm = emulator.pbm(&ONE_CHAR, &PIXELS);
+            assert_eq!(body(&pbm, 2).last(), Some(&0xF8));
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::VecDeque;
+
+use super::decoder::{Operation, Transaction, TransactionDecoder};
+use crate::lcd_trait::{Interface, ReadInterface, _RW};
+
+/// ### mock `Interface`
+/// records each `send` and decodes them into operations for each enable,
+/// with assertions on the commands and the text.
+/// the reads return the bytes queued with `push_read` (0 when empty)
+///
+/// ```
+/// # use liquid_crystal::prelude::*;
+/// # use liquid_crystal::emulator::*;
+/// # let mut delay = VirtualDelay::default();
+/// let mut mock = MockInterface::new();
+/// let mut lcd = LiquidCrystal::new(&mut mock, Bus4Bits, LCD16X2);
+/// lcd.begin(&mut delay);
+/// lcd.interface().clear();
+/// lcd.set_cursor(&mut delay, 1, 0).write(&mut delay, Text("hello"));
+/// mock.expect_commands(&[0xC0]);
+/// mock.expect_text("hello");
+/// ```
+pub struct MockInterface {
+    decoder: TransactionDecoder,
+    sends: Vec<(u8, u8)>,
+    transactions: Vec<Transaction>,
+    reads: VecDeque<u8>,
+    config: u8,
+}
+
+impl MockInterface {
+    pub fn new() -> MockInterface {
+        MockInterface {
+            decoder: TransactionDecoder::new(),
+            sends: Vec::new(),
+            transactions: Vec::new(),
+            reads: VecDeque::new(),
+            config: 0,
+        }
+    }
+
+    /// ### forgets the records
+    /// the bus mode (4/8 bits) is kept
+    pub fn clear(&mut self) {
+        self.sends.clear();
+        self.transactions.clear();
+    }
+
+    /// ### queues a byte for the next read
+    pub fn push_read(&mut self, data: u8) {
+        self.reads.push_back(data);
+    }
+
+    /// ### the `config`/`data` pairs sent
+    pub fn sends(&self) -> &[(u8, u8)] {
+        &self.sends
+    }
+
+    /// ### the decoded operations
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.transactions
+    }
+
+    /// ### the commands sent (RS = 0)
+    pub fn commands(&self) -> Vec<u8> {
+        self.transactions
+            .iter()
+            .filter_map(|transaction| match transaction.operation {
+                Operation::Command(command) => Some(command),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// ### the bytes written in the RAM (RS = 1)
+    pub fn data(&self) -> Vec<u8> {
+        self.transactions
+            .iter()
+            .filter_map(|transaction| match transaction.operation {
+                Operation::Data(data) => Some(data),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// ### the bytes written in the RAM as text
+    pub fn text(&self) -> String {
+        self.data().into_iter().map(char::from).collect()
+    }
+
+    /// ### panics if the commands sent are not `commands`
+    #[track_caller]
+    pub fn expect_commands(&self, commands: &[u8]) {
+        assert_eq!(self.commands(), commands, "commands sent");
+    }
+
+    /// ### panics if the bytes written are not `text`
+    #[track_caller]
+    pub fn expect_text(&self, text: &str) {
+        assert_eq!(self.text(), text, "text written");
+    }
+
+    /// ### panics if the operations are not `transactions`
+    #[track_caller]
+    pub fn expect_transactions(&self, transactions: &[Transaction]) {
+        assert_eq!(self.transactions, transactions, "transactions");
+    }
+}
+
+impl Default for MockInterface {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interface for MockInterface {
+    fn send(&mut self, config: u8, data: u8) {
+        self.sends.push((config, data));
+        self.config = config;
+        let transactions = self.decoder.decode(config, data);
+        let read = transactions
+            .iter()
+            .any(|transaction| matches!(transaction.operation, Operation::Read { .. }));
+        self.transactions.extend(transactions);
+        if read {
+            self.reads.pop_front();
+        }
+    }
+}
+
+impl ReadInterface for MockInterface {
+    fn read(&mut self) -> u8 {
+        let Some(decoder) = self
+            .decoder
+            .decoders()
+            .iter()
+            .find(|decoder| decoder.enable_high(self.config))
+        else {
+            return 0;
+        };
+        if (self.config & _RW) == 0 {
+            return 0;
+        }
+        let byte = self.reads.front().copied().unwrap_or(0);
+        if decoder.eight_bits() {
+            byte
+        } else if decoder.nibble_pending() {
+            byte << 4
+        } else {
+            byte & 0xF0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::VirtualDelay;
+    use crate::lcd_trait::{BusBits, LiquidCrystal, SendType, EN, LCD16X2};
+
+    /// mock after `begin` and "hi" on the line 2 of the controller `lcd` (0, 1 or both)
+    fn mock(bus: BusBits, lcd: Option<u8>) -> MockInterface {
+        let mut delay = VirtualDelay::default();
+        let mut mock = MockInterface::new();
+        let mut driver = LiquidCrystal::new(&mut mock, bus, LCD16X2);
+        driver.begin(&mut delay);
+        driver.interface().clear();
+        if let Some(lcd) = lcd {
+            driver.select_lcd(lcd);
+        }
+        driver
+            .set_cursor(&mut delay, 1, 0)
+            .write(&mut delay, SendType::Text("hi"));
+        mock
+    }
+
+    fn transactions(enable: u8) -> [Transaction; 3] {
+        [
+            Transaction {
+                enable,
+                operation: Operation::Command(0xC0),
+            },
+            Transaction {
+                enable,
+                operation: Operation::Data(b'h'),
+            },
+            Transaction {
+                enable,
+                operation: Operation::Data(b'i'),
+            },
+        ]
+    }
+
+    #[test]
+    fn matching_traffic() {
+        for bus in [BusBits::Bus4Bits, BusBits::Bus8Bits] {
+            let mock = mock(bus, Some(0));
+            mock.expect_commands(&[0xC0]);
+            mock.expect_text("hi");
+            mock.expect_transactions(&transactions(EN));
+        }
+    }
+
+    #[test]
+    fn second_controller() {
+        let mock = mock(BusBits::Bus4Bits, Some(1));
+        mock.expect_commands(&[0xC0]);
+        mock.expect_text("hi");
+        mock.expect_transactions(&transactions(EN << 1));
+    }
+
+    #[test]
+    fn both_controllers() {
+        let mock = mock(BusBits::Bus4Bits, None);
+        mock.expect_transactions(&transactions(EN | (EN << 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "commands sent")]
+    fn wrong_commands() {
+        mock(BusBits::Bus4Bits, Some(0)).expect_commands(&[0x80]);
+    }
+
+    #[test]
+    #[should_panic(expected = "text written")]
+    fn wrong_text() {
+        mock(BusBits::Bus8Bits, Some(0)).expect_text("ho");
+    }
+
+    #[test]
+    #[should_panic(expected = "transactions")]
+    fn wrong_transactions() {
+        mock(BusBits::Bus4Bits, Some(0)).expect_transactions(&transactions(EN)[..2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "transactions")]
+    fn wrong_controller() {
+        mock(BusBits::Bus4Bits, Some(1)).expect_transactions(&transactions(EN));
+    }
+
+    #[test]
+    fn queued_reads() {
+        let mut delay = VirtualDelay::default();
+        let mut mock = MockInterface::new();
+        let mut driver = LiquidCrystal::new(&mut mock, BusBits::Bus4Bits, LCD16X2);
+        driver.begin(&mut delay);
+        driver.select_lcd(0);
+        driver.interface().push_read(b'a');
+        driver.interface().push_read(0x45);
+
+        assert_eq!(driver.read_data(&mut delay), b'a');
+        assert_eq!(driver.read_address(&mut delay), 0x45);
+        assert_eq!(driver.read_data(&mut delay), 0);
+    }
+}
@@ -0,0 +1,27 @@
+//! # host tools (feature `std`)
+//! emulator of the HD44780 and tools to test the display code without hardware:
+//! timing checker, mock interface, text/ANSI rendering, image export, record/replay,
+//! VCD export, decoding of logic analyzer captures and an emulated PCF8574 I2C backpack
+mod capture;
+mod decoder;
+mod hd44780;
+mod image;
+mod mock;
+mod pcf8574;
+mod render;
+mod replay;
+mod rom;
+mod timing;
+mod vcd;
+
+pub use capture::*;
+pub use decoder::*;
+pub use hd44780::*;
+pub use image::*;
+pub use mock::*;
+pub use pcf8574::*;
+pub use render::*;
+pub use replay::*;
+pub use rom::*;
+pub use timing::*;
+pub use vcd::*;
@@ -0,0 +1,176 @@
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+
+use super::hd44780::HD44780;
+use crate::lcd_trait::{Interface, ReadInterface, PCF8574_BACKLIGHT, PCF8574_BACKPACK};
+
+/// ### I2C bus with an emulated PCF8574 backpack
+/// acknowledges only `address`, each byte written is latched on the port and the pins
+/// (wired as `PCF8574_BACKPACK`) drive the HD44780 emulator.
+/// the reads return the port, with the data pins driven by the display while RW is high
+///
+/// ```
+/// # use liquid_crystal::prelude::*;
+/// # use liquid_crystal::emulator::*;
+/// # use liquid_crystal::I2C;
+/// # let mut delay = VirtualDelay::default();
+/// let mut bus = PCF8574::new(0x27);
+/// let mut interface = I2C::new(&mut bus, 0x27);
+/// let mut lcd = LiquidCrystal::new(&mut interface, Bus4Bits, LCD16X2);
+/// lcd.begin(&mut delay);
+/// lcd.write(&mut delay, Text("hello"));
+/// assert_eq!(bus.emulator().line(0), "hello");
+/// assert!(bus.backlight());
+/// ```
+pub struct PCF8574 {
+    address: u8,
+    port: u8,
+    emulator: HD44780,
+}
+
+impl PCF8574 {
+    /// the port starts high, like the PCF8574 after power on
+    pub fn new(address: u8) -> PCF8574 {
+        PCF8574 {
+            address,
+            port: 0xFF,
+            emulator: HD44780::new(),
+        }
+    }
+
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// ### last byte written
+    pub fn port(&self) -> u8 {
+        self.port
+    }
+
+    /// ### state of the backlight bit (P3)
+    pub fn backlight(&self) -> bool {
+        (self.port & PCF8574_BACKLIGHT) != 0
+    }
+
+    pub fn emulator(&mut self) -> &mut HD44780 {
+        &mut self.emulator
+    }
+
+    pub fn release(self) -> HD44780 {
+        self.emulator
+    }
+
+    fn write_port(&mut self, port: u8) {
+        self.port = port;
+        let config = PCF8574_BACKPACK.unpack_config(port as u16);
+        let data = PCF8574_BACKPACK.unpack_data(port as u16);
+        self.emulator.send(config, data);
+    }
+
+    /// a pin reads low if the port or the display drives it low
+    fn read_port(&mut self) -> u8 {
+        let display = PCF8574_BACKPACK.pack(0, self.emulator.read()) as u8;
+        let data_pins = PCF8574_BACKPACK.pack(0, 0xFF) as u8;
+        self.port & (display | !data_pins)
+    }
+}
+
+impl ErrorType for PCF8574 {
+    type Error = ErrorKind;
+}
+
+impl I2c for PCF8574 {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address != self.address {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    for byte in bytes.iter() {
+                        self.write_port(*byte);
+                    }
+                }
+                Operation::Read(buffer) => {
+                    for byte in buffer.iter_mut() {
+                        *byte = self.read_port();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::VirtualDelay;
+    use crate::lcd_trait::{BusBits, LiquidCrystal, SendType, I2C, LCD16X2};
+
+    /// `begin`, "hello" on the line 1 and "I2C" on the line 2, through `I2C` at `address`
+    fn write_hello(bus: &mut PCF8574, address: u8) {
+        let mut delay = VirtualDelay::default();
+        let mut interface = I2C::new(bus, address);
+        let mut lcd = LiquidCrystal::new(&mut interface, BusBits::Bus4Bits, LCD16X2);
+        lcd.begin(&mut delay);
+        lcd.write(&mut delay, SendType::Text("hello"));
+        lcd.set_cursor(&mut delay, 1, 2)
+            .write(&mut delay, SendType::Text("I2C"));
+    }
+
+    #[test]
+    fn drives_the_display() {
+        let mut bus = PCF8574::new(0x27);
+        write_hello(&mut bus, 0x27);
+
+        assert_eq!(bus.emulator().line(0), "hello");
+        assert_eq!(bus.emulator().line(1), "  I2C");
+        assert!(!bus.emulator().eight_bits());
+        assert!(bus.backlight());
+        // low nibble of 'C' (0x43), RS, backlight, EN low
+        assert_eq!(bus.port(), 0x39);
+    }
+
+    #[test]
+    fn backlight_bit() {
+        let mut bus = PCF8574::new(0x3F);
+        bus.write(0x3F, &[0x00]).unwrap();
+        assert!(!bus.backlight());
+        bus.write(0x3F, &[PCF8574_BACKLIGHT]).unwrap();
+        assert!(bus.backlight());
+    }
+
+    #[test]
+    fn nacks_other_addresses() {
+        let mut bus = PCF8574::new(0x27);
+        assert_eq!(
+            bus.write(0x3F, &[0x00]),
+            Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+        );
+
+        write_hello(&mut bus, 0x3F);
+        assert_eq!(bus.port(), 0xFF);
+        assert_eq!(bus.emulator().line(0), "");
+    }
+
+    #[test]
+    fn reads_the_display() {
+        let mut bus = PCF8574::new(0x27);
+        write_hello(&mut bus, 0x27);
+        let mut port = [0];
+        // busy flag and address, RW and EN high, data pins released
+        bus.write(0x27, &[0xF0 | PCF8574_BACKLIGHT | 0x04 | 0x02])
+            .unwrap();
+        bus.read(0x27, &mut port).unwrap();
+        assert_eq!(port[0], 0x4E);
+        bus.write(0x27, &[0xF0 | PCF8574_BACKLIGHT | 0x02]).unwrap();
+        bus.write(0x27, &[0xF0 | PCF8574_BACKLIGHT | 0x04 | 0x02])
+            .unwrap();
+        bus.read(0x27, &mut port).unwrap();
+        assert_eq!(port[0], 0x5E);
+    }
+}
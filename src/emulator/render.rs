@@ -0,0 +1,270 @@
+use super::hd44780::HD44780;
+use crate::lcd_trait::Layout;
+
+/// ### colors of the ANSI view
+#[derive(Clone, Copy)]
+pub enum Backlight {
+    /// dark text on yellow green
+    Green,
+    /// white text on blue
+    Blue,
+}
+
+impl Backlight {
+    /// 256 colors (foreground, background)
+    fn colors(self) -> (u8, u8) {
+        match self {
+            Backlight::Green => (22, 148),
+            Backlight::Blue => (231, 21),
+        }
+    }
+}
+
+/// ### character shown for a byte of the DDRAM
+/// the custom characters are ⓪ - ⑦, the codes without ASCII/Latin-1 equivalent are spaces
+pub fn display_char(byte: u8) -> char {
+    match byte {
+        0x00 => '⓪',
+        0x01..=0x07 => char::from_u32(0x2460 + byte as u32 - 1).unwrap_or(' '),
+        0x08..=0x0F => display_char(byte - 8),
+        0x20..=0x7E | 0xA0..=0xFF => char::from(byte),
+        _ => ' ',
+    }
+}
+
+impl HD44780 {
+    /// ### DDRAM address shown at `column` of the line that starts at `line_address`
+    /// (address of the layout, with or without 0x80), with the display shift
+    pub fn visible_address(&self, line_address: u8, column: u8) -> u8 {
+        let address = line_address & 0x7F;
+        let shift = self.shift() as u16;
+        if self.two_lines() {
+            let offset = ((address & 0x3F) as u16 + column as u16 + shift) % 40;
+            (address & 0x40) | offset as u8
+        } else {
+            ((address as u16 + column as u16 + shift) % 80) as u8
+        }
+    }
+
+    /// ### bytes shown on each line of `layout`
+    /// spaces while the display is off
+    pub fn visible_bytes<const COLS: u8, const LINES: usize>(
+        &self,
+        layout: &Layout<COLS, LINES>,
+    ) -> Vec<Vec<u8>> {
+        layout
+            .addrs
+            .iter()
+            .map(|address| {
+                (0..COLS)
+                    .map(|column| {
+                        if self.display_on() {
+                            self.ddram()[self.visible_address(*address, column) as usize]
+                        } else {
+                            b' '
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// ### text shown on each line of `layout` (see `display_char`)
+    pub fn visible_lines<const COLS: u8, const LINES: usize>(
+        &self,
+        layout: &Layout<COLS, LINES>,
+    ) -> Vec<String> {
+        self.visible_bytes(layout)
+            .iter()
+            .map(|line| line.iter().map(|byte| display_char(*byte)).collect())
+            .collect()
+    }
+
+    /// ### line and column of the cursor in `layout`
+    /// `None` if the cursor is out of the window or points to the CGRAM
+    pub fn cursor_position<const COLS: u8, const LINES: usize>(
+        &self,
+        layout: &Layout<COLS, LINES>,
+    ) -> Option<(usize, u8)> {
+        if self.cgram_selected() {
+            return None;
+        }
+        layout.addrs.iter().enumerate().find_map(|(line, address)| {
+            (0..COLS)
+                .find(|column| self.visible_address(*address, *column) == self.address())
+                .map(|column| (line, column))
+        })
+    }
+
+    /// ### boxed text of the visible window, for golden files
+    /// the blinking cursor is shown as '█' and the underline cursor as '_'
+    pub fn snapshot<const COLS: u8, const LINES: usize>(
+        &self,
+        layout: &Layout<COLS, LINES>,
+    ) -> String {
+        let border = format!("+{}+\n", "-".repeat(COLS as usize));
+        let cursor = self.visible_cursor(layout);
+        let mut text = border.clone();
+        for (n, line) in self.visible_lines(layout).iter().enumerate() {
+            text.push('|');
+            for (column, char) in line.chars().enumerate() {
+                text.push(match cursor {
+                    Some((l, c, true)) if (l, c as usize) == (n, column) => '█',
+                    Some((l, c, false)) if (l, c as usize) == (n, column) => '_',
+                    _ => char,
+                });
+            }
+            text.push_str("|\n");
+        }
+        text.push_str(&border);
+        text
+    }
+
+    /// ### ANSI colored view of the visible window
+    /// the cursor is underlined, the blinking cursor is a blinking block
+    pub fn ansi<const COLS: u8, const LINES: usize>(
+        &self,
+        layout: &Layout<COLS, LINES>,
+        backlight: Backlight,
+    ) -> String {
+        let (foreground, background) = backlight.colors();
+        let color = format!("\x1b[38;5;{};48;5;{}m", foreground, background);
+        let padding = format!("{}{}\x1b[0m\n", color, " ".repeat(COLS as usize + 2));
+        let cursor = self.visible_cursor(layout);
+        let mut text = padding.clone();
+        for (n, line) in self.visible_lines(layout).iter().enumerate() {
+            text.push_str(&color);
+            text.push(' ');
+            for (column, char) in line.chars().enumerate() {
+                match cursor {
+                    Some((l, c, blink)) if (l, c as usize) == (n, column) => {
+                        let style = if blink { "\x1b[5;7m" } else { "\x1b[4m" };
+                        text.push_str(&format!("{}{}\x1b[24;25;27m", style, char));
+                    }
+                    _ => text.push(char),
+                }
+            }
+            text.push_str(" \x1b[0m\n");
+        }
+        text.push_str(&padding);
+        text
+    }
+
+    /// position of the cursor if shown, true if blinking
+    fn visible_cursor<const COLS: u8, const LINES: usize>(
+        &self,
+        layout: &Layout<COLS, LINES>,
+    ) -> Option<(usize, u8, bool)> {
+        if !self.display_on() || !(self.cursor_on() || self.blink_on()) {
+            return None;
+        }
+        self.cursor_position(layout)
+            .map(|(line, column)| (line, column, self.blink_on()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcd_trait::LCD16X2;
+
+    /// 2 lines, display on, "hello" and "World", 'Z' and 'Y' at the end of the lines
+    fn emulator() -> HD44780 {
+        let mut emulator = HD44780::new();
+        emulator.command(0x28);
+        emulator.command(0x0C);
+        for (address, text) in [(0x80, "hello"), (0xC0, "World"), (0xA7, "Z"), (0xE7, "Y")] {
+            emulator.command(address);
+            text.bytes().for_each(|byte| emulator.write_data(byte));
+        }
+        emulator.command(0x80);
+        emulator
+    }
+
+    fn boxed(lines: [&str; 2]) -> String {
+        format!(
+            "+----------------+\n|{:16}|\n|{:16}|\n+----------------+\n",
+            lines[0], lines[1]
+        )
+    }
+
+    #[test]
+    fn text_on_each_line() {
+        let emulator = emulator();
+        assert_eq!(emulator.snapshot(&LCD16X2), boxed(["hello", "World"]));
+        assert_eq!(
+            emulator.visible_lines(&LCD16X2)[1],
+            format!("{:16}", "World")
+        );
+    }
+
+    #[test]
+    fn custom_characters() {
+        let mut emulator = emulator();
+        emulator.command(0x85);
+        emulator.write_data(0x00);
+        emulator.write_data(0x0F);
+        assert_eq!(emulator.snapshot(&LCD16X2), boxed(["hello⓪⑦", "World"]));
+    }
+
+    #[test]
+    fn display_shift() {
+        let mut emulator = emulator();
+        emulator.command(0x18); // shift left
+        assert_eq!(emulator.visible_address(0x80, 0), 0x01);
+        assert_eq!(emulator.snapshot(&LCD16X2), boxed(["ello", "orld"]));
+
+        emulator.command(0x1C);
+        emulator.command(0x1C); // shift right, past 0x27 and 0x67
+        assert_eq!(emulator.shift(), 39);
+        assert_eq!(emulator.visible_address(0x80, 0), 0x27);
+        assert_eq!(emulator.visible_address(0xC0, 0), 0x67);
+        assert_eq!(emulator.visible_address(0xC0, 1), 0x40);
+        assert_eq!(emulator.snapshot(&LCD16X2), boxed(["Zhello", "YWorld"]));
+    }
+
+    #[test]
+    fn display_off() {
+        let mut emulator = emulator();
+        emulator.command(0x0B);
+        assert_eq!(emulator.snapshot(&LCD16X2), boxed(["", ""]));
+        assert_eq!(
+            emulator
+                .ansi(&LCD16X2, Backlight::Green)
+                .matches('h')
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn cursor() {
+        let mut emulator = emulator();
+        emulator.command(0xC2);
+        emulator.command(0x0E); // underline
+        assert_eq!(emulator.cursor_position(&LCD16X2), Some((1, 2)));
+        assert_eq!(emulator.snapshot(&LCD16X2), boxed(["hello", "Wo_ld"]));
+        assert!(emulator
+            .ansi(&LCD16X2, Backlight::Blue)
+            .contains("Wo\x1b[4mr\x1b[24;25;27mld"));
+
+        emulator.command(0x0D); // blink
+        assert_eq!(emulator.snapshot(&LCD16X2), boxed(["hello", "Wo█ld"]));
+        assert!(emulator
+            .ansi(&LCD16X2, Backlight::Blue)
+            .contains("Wo\x1b[5;7mr\x1b[24;25;27mld"));
+
+        emulator.command(0x18); // out of the window
+        emulator.command(0xC0);
+        assert_eq!(emulator.cursor_position(&LCD16X2), None);
+        assert_eq!(emulator.snapshot(&LCD16X2), boxed(["ello", "orld"]));
+    }
+
+    #[test]
+    fn ansi_colors() {
+        let ansi = emulator().ansi(&LCD16X2, Backlight::Green);
+        let lines: Vec<&str> = ansi.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "\x1b[38;5;22;48;5;148m hello            \x1b[0m");
+    }
+}
@@ -0,0 +1,426 @@
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use super::decoder::{Operation, Transaction, TransactionDecoder};
+use crate::lcd_trait::{BusBits, DelayNs, Interface, InterfaceKind, EN};
+
+const MAGIC: &[u8; 4] = b"LCRP";
+/// ### version of the recording format
+pub const RECORDING_VERSION: u8 = 1;
+
+/// ### content of a recording
+/// each entry is 2 bytes: `config` and `data` with the bits of `Interface::send`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// the sends of a pins interface (enable pulses and nibbles)
+    PinSends = 0,
+    /// the sends of a `Bytes` interface (whole bytes, RS and RW)
+    ByteSends = 1,
+    /// the decoded operations (`Transaction::to_pair`)
+    Operations = 2,
+}
+
+#[derive(Debug)]
+pub enum RecordingError {
+    /// not a recording
+    BadMagic,
+    UnsupportedVersion(u8),
+    UnknownFormat(u8),
+    /// odd number of bytes
+    Truncated,
+    Io(io::Error),
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordingError::BadMagic => write!(f, "not a recording"),
+            RecordingError::UnsupportedVersion(version) => {
+                write!(f, "unsupported recording version {}", version)
+            }
+            RecordingError::UnknownFormat(format) => write!(f, "unknown record format {}", format),
+            RecordingError::Truncated => write!(f, "truncated recording"),
+            RecordingError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+impl From<io::Error> for RecordingError {
+    fn from(error: io::Error) -> Self {
+        RecordingError::Io(error)
+    }
+}
+
+/// ### traffic of an interface
+/// serialized as "LCRP", the version, the format and the entries
+///
+/// ```no_run
+/// # use liquid_crystal::prelude::*;
+/// # use liquid_crystal::emulator::*;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let mut delay = VirtualDelay::default();
+/// # let mut interface = HD44780::new();
+/// let recording = Recording::from_bytes(&std::fs::read("field.lcrp")?)?;
+/// recording.replay(&mut interface, &mut delay, Bus4Bits);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recording {
+    format: RecordFormat,
+    entries: Vec<(u8, u8)>,
+}
+
+impl Recording {
+    pub fn new(format: RecordFormat) -> Recording {
+        Recording {
+            format,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn format(&self) -> RecordFormat {
+        self.format
+    }
+
+    /// ### the `config`/`data` pairs
+    pub fn entries(&self) -> &[(u8, u8)] {
+        &self.entries
+    }
+
+    pub fn push(&mut self, config: u8, data: u8) {
+        self.entries.push((config, data));
+    }
+
+    /// ### the operations of the recording
+    pub fn transactions(&self) -> Vec<Transaction> {
+        match self.format {
+            RecordFormat::PinSends => {
+                let mut decoder = TransactionDecoder::new();
+                self.entries
+                    .iter()
+                    .flat_map(|(config, data)| decoder.decode(*config, *data))
+                    .collect()
+            }
+            RecordFormat::ByteSends => self
+                .entries
+                .iter()
+                .map(|(config, data)| Transaction::from_pair(*config | EN, *data))
+                .collect(),
+            RecordFormat::Operations => self
+                .entries
+                .iter()
+                .map(|(config, data)| Transaction::from_pair(*config, *data))
+                .collect(),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(6 + 2 * self.entries.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(RECORDING_VERSION);
+        bytes.push(self.format as u8);
+        for (config, data) in &self.entries {
+            bytes.push(*config);
+            bytes.push(*data);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Recording, RecordingError> {
+        if bytes.len() < 6 || &bytes[..4] != MAGIC {
+            return Err(RecordingError::BadMagic);
+        }
+        if bytes[4] != RECORDING_VERSION {
+            return Err(RecordingError::UnsupportedVersion(bytes[4]));
+        }
+        let format = match bytes[5] {
+            0 => RecordFormat::PinSends,
+            1 => RecordFormat::ByteSends,
+            2 => RecordFormat::Operations,
+            format => return Err(RecordingError::UnknownFormat(format)),
+        };
+        let entries = &bytes[6..];
+        if !entries.len().is_multiple_of(2) {
+            return Err(RecordingError::Truncated);
+        }
+        Ok(Recording {
+            format,
+            entries: entries.chunks(2).map(|pair| (pair[0], pair[1])).collect(),
+        })
+    }
+
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+
+    pub fn read_from(mut reader: impl Read) -> Result<Recording, RecordingError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Recording::from_bytes(&bytes)
+    }
+
+    /// ### sends the recording to `interface`
+    /// the pin sends are replayed as they are on pins interfaces, the rest is sent
+    /// operation by operation, with the function sets adapted to `bus` on pins interfaces.
+    /// waits the execution time after each operation
+    pub fn replay<T: Interface>(&self, interface: &mut T, delay: &mut impl DelayNs, bus: BusBits) {
+        delay.delay_ms(50);
+        if T::KIND == InterfaceKind::Pins && self.format == RecordFormat::PinSends {
+            let mut decoder = TransactionDecoder::new();
+            for (config, data) in &self.entries {
+                interface.send(*config, *data);
+                delay.delay_us(1);
+                for transaction in decoder.decode(*config, *data) {
+                    wait_execution(delay, transaction.operation);
+                }
+            }
+            return;
+        }
+        let eight_bits_bus = matches!(bus, BusBits::Bus8Bits);
+        let mut eight_bits = true;
+        for transaction in self.transactions() {
+            let (config, mut data) = transaction.to_pair();
+            let function_set = matches!(transaction.operation, Operation::Command(0x20..=0x3F));
+            if T::KIND == InterfaceKind::Bytes {
+                if !matches!(transaction.operation, Operation::Read { .. }) {
+                    interface.send(config & !(EN | (EN << 1)), data);
+                }
+            } else {
+                // the function sets follow `bus`, except the reset sequence (0x30)
+                if function_set && data != 0x30 {
+                    data = if eight_bits_bus {
+                        data | 0x10
+                    } else {
+                        data & !0x10
+                    };
+                    if eight_bits && !eight_bits_bus && data != 0x20 {
+                        pulse(interface, delay, config, 0x20);
+                        delay.delay_us(40);
+                        eight_bits = false;
+                    }
+                }
+                if eight_bits || eight_bits_bus {
+                    pulse(interface, delay, config, data);
+                } else {
+                    pulse(interface, delay, config, data & 0xF0);
+                    pulse(interface, delay, config, data << 4);
+                }
+            }
+            if function_set {
+                eight_bits = (data & 0x10) != 0;
+            }
+            wait_execution(delay, transaction.operation);
+        }
+    }
+}
+
+/// ### records the traffic of an interface
+/// forwards the sends to `interface`
+pub struct Recorder<T: Interface> {
+    interface: T,
+    decoder: Option<TransactionDecoder>,
+    recording: Recording,
+}
+
+impl<T: Interface> Recorder<T> {
+    /// ### records the sends as they are
+    pub fn sends(interface: T) -> Recorder<T> {
+        let format = match T::KIND {
+            InterfaceKind::Pins => RecordFormat::PinSends,
+            InterfaceKind::Bytes => RecordFormat::ByteSends,
+        };
+        Recorder {
+            interface,
+            decoder: None,
+            recording: Recording::new(format),
+        }
+    }
+
+    /// ### records the decoded operations
+    pub fn operations(interface: T) -> Recorder<T> {
+        Recorder {
+            interface,
+            decoder: Some(TransactionDecoder::new()),
+            recording: Recording::new(RecordFormat::Operations),
+        }
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    pub fn interface(&mut self) -> &mut T {
+        &mut self.interface
+    }
+
+    pub fn release(self) -> (T, Recording) {
+        (self.interface, self.recording)
+    }
+
+    fn record(&mut self, config: u8, data: u8) {
+        match (&mut self.decoder, T::KIND) {
+            (None, _) => self.recording.push(config, data),
+            (Some(_), InterfaceKind::Bytes) => self.recording.push(config | EN, data),
+            (Some(decoder), InterfaceKind::Pins) => {
+                for transaction in decoder.decode(config, data) {
+                    let (config, data) = transaction.to_pair();
+                    self.recording.push(config, data);
+                }
+            }
+        }
+    }
+}
+
+impl<T: Interface> Interface for Recorder<T> {
+    const KIND: InterfaceKind = T::KIND;
+
+    fn send(&mut self, config: u8, data: u8) {
+        self.record(config, data);
+        self.interface.send(config, data);
+    }
+
+    fn send_bytes(&mut self, config: u8, data: &[u8]) {
+        for byte in data {
+            self.record(config, *byte);
+        }
+        self.interface.send_bytes(config, data);
+    }
+}
+
+/// sends `data` with a pulse of the enables of `config`
+fn pulse<T: Interface>(interface: &mut T, delay: &mut impl DelayNs, config: u8, data: u8) {
+    let pins = config & !(EN | (EN << 1));
+    interface.send(pins, data);
+    interface.send(config, data);
+    delay.delay_us(1);
+    interface.send(pins, data);
+    delay.delay_us(1);
+}
+
+fn wait_execution(delay: &mut impl DelayNs, operation: Operation) {
+    match operation {
+        Operation::Command(0x01..=0x03) => delay.delay_us(2000),
+        // function set in 8 bits, maybe the reset sequence
+        Operation::Command(0x30..=0x3F) => delay.delay_us(4100),
+        _ => delay.delay_us(40),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::{VirtualDelay, HD44780};
+    use crate::lcd_trait::{LiquidCrystal, SendType, LCD16X2};
+
+    /// `begin`, "hello" and "World!" on the line 2
+    fn drive<T: Interface>(interface: &mut T, bus: BusBits) {
+        let mut delay = VirtualDelay::default();
+        let mut lcd = LiquidCrystal::new(interface, bus, LCD16X2);
+        lcd.begin(&mut delay);
+        lcd.write(&mut delay, SendType::Text("hello"));
+        lcd.set_cursor(&mut delay, 1, 0)
+            .write(&mut delay, SendType::Text("World!"));
+    }
+
+    fn record(operations: bool, bus: BusBits) -> Recording {
+        let mut recorder = if operations {
+            Recorder::operations(HD44780::new())
+        } else {
+            Recorder::sends(HD44780::new())
+        };
+        drive(&mut recorder, bus);
+        recorder.release().1
+    }
+
+    fn replay(recording: &Recording, bus: BusBits) -> HD44780 {
+        let mut emulator = HD44780::new();
+        recording.replay(&mut emulator, &mut VirtualDelay::default(), bus);
+        emulator
+    }
+
+    #[test]
+    fn round_trip() {
+        for operations in [false, true] {
+            let recording = record(operations, BusBits::Bus4Bits);
+            let bytes = recording.to_bytes();
+            assert_eq!(
+                &bytes[..6],
+                &[b'L', b'C', b'R', b'P', 1, recording.format() as u8]
+            );
+            assert_eq!(Recording::from_bytes(&bytes).unwrap(), recording);
+
+            let mut file = Vec::new();
+            recording.write_to(&mut file).unwrap();
+            assert_eq!(Recording::read_from(file.as_slice()).unwrap(), recording);
+        }
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(
+            record(false, BusBits::Bus4Bits).format(),
+            RecordFormat::PinSends
+        );
+        let recording = record(true, BusBits::Bus8Bits);
+        assert_eq!(recording.format(), RecordFormat::Operations);
+        // both enables (`echo`)
+        assert_eq!(recording.entries()[..2], [(0x0C, 0x30), (0x0C, 0x30)]);
+        assert_eq!(
+            recording.transactions(),
+            record(false, BusBits::Bus8Bits).transactions()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let bytes = record(true, BusBits::Bus4Bits).to_bytes();
+        let error = |bytes: &[u8]| Recording::from_bytes(bytes).unwrap_err();
+
+        assert!(matches!(error(b"LCR"), RecordingError::BadMagic));
+        assert!(matches!(error(b"RIFF\x01\x00"), RecordingError::BadMagic));
+        let mut version = bytes.clone();
+        version[4] = 2;
+        assert!(matches!(
+            error(&version),
+            RecordingError::UnsupportedVersion(2)
+        ));
+        let mut format = bytes.clone();
+        format[5] = 7;
+        assert!(matches!(error(&format), RecordingError::UnknownFormat(7)));
+        assert!(matches!(
+            error(&bytes[..bytes.len() - 1]),
+            RecordingError::Truncated
+        ));
+        assert_eq!(
+            error(b"LCRP\x09\x00").to_string(),
+            "unsupported recording version 9"
+        );
+    }
+
+    #[test]
+    fn replays_on_the_other_bus() {
+        for (recorded, replayed, eight_bits) in [
+            (BusBits::Bus4Bits, BusBits::Bus8Bits, true),
+            (BusBits::Bus8Bits, BusBits::Bus4Bits, false),
+        ] {
+            let emulator = replay(&record(true, recorded), replayed);
+            assert_eq!(emulator.line(0), "hello");
+            assert_eq!(emulator.line(1), "World!");
+            assert_eq!(emulator.eight_bits(), eight_bits);
+            assert!(emulator.two_lines());
+        }
+    }
+
+    #[test]
+    fn replays_the_pin_sends() {
+        for (bus, eight_bits) in [(BusBits::Bus4Bits, false), (BusBits::Bus8Bits, true)] {
+            let emulator = replay(&record(false, bus), BusBits::Bus8Bits);
+            assert_eq!(emulator.line(0), "hello");
+            assert_eq!(emulator.line(1), "World!");
+            assert_eq!(emulator.eight_bits(), eight_bits);
+        }
+    }
+}
@@ -0,0 +1,591 @@
+/// ### character ROMs of the HD44780U
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rom {
+    /// japanese: katakana and symbols
+    A00,
+    /// european: latin-1, cyrillic and greek
+    A02,
+}
+
+impl Rom {
+    /// ### 5x8 glyph of `code`
+    /// rows from top to bottom, bit 4 on the left, the last row is left for the cursor
+    pub fn glyph_5x8(self, code: u8) -> [u8; 8] {
+        match self {
+            Rom::A00 => ROM_A00[code as usize],
+            Rom::A02 => ROM_A02[code as usize],
+        }
+    }
+
+    /// ### 5x10 glyph of `code`
+    /// 11 rows with the cursor, the glyphs without a 5x10 version are the 5x8 ones
+    pub fn glyph_5x10(self, code: u8) -> [u8; 11] {
+        if self == Rom::A00 && code >= 0xE0 {
+            return ROM_A00_5X10[code as usize - 0xE0];
+        }
+        let mut glyph = [0; 11];
+        glyph[..8].copy_from_slice(&self.glyph_5x8(code));
+        glyph
+    }
+}
+
+/// ### 5x8 glyphs of the A00 ROM
+/// 0x00 - 0x0F are the CGRAM, 0xE0 - 0xFF are the 5x8 versions of `ROM_A00_5X10`
+#[rustfmt::skip]
+pub const ROM_A00: [[u8; 8]; 256] = [
+    [0; 8], // 0x00
+    [0; 8], // 0x01
+    [0; 8], // 0x02
+    [0; 8], // 0x03
+    [0; 8], // 0x04
+    [0; 8], // 0x05
+    [0; 8], // 0x06
+    [0; 8], // 0x07
+    [0; 8], // 0x08
+    [0; 8], // 0x09
+    [0; 8], // 0x0A
+    [0; 8], // 0x0B
+    [0; 8], // 0x0C
+    [0; 8], // 0x0D
+    [0; 8], // 0x0E
+    [0; 8], // 0x0F
+    [0; 8], // 0x10
+    [0; 8], // 0x11
+    [0; 8], // 0x12
+    [0; 8], // 0x13
+    [0; 8], // 0x14
+    [0; 8], // 0x15
+    [0; 8], // 0x16
+    [0; 8], // 0x17
+    [0; 8], // 0x18
+    [0; 8], // 0x19
+    [0; 8], // 0x1A
+    [0; 8], // 0x1B
+    [0; 8], // 0x1C
+    [0; 8], // 0x1D
+    [0; 8], // 0x1E
+    [0; 8], // 0x1F
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x20 space
+    [0x04, 0x04, 0x04, 0x04, 0x00, 0x00, 0x04, 0x00], // 0x21 !
+    [0x0A, 0x0A, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x22 "
+    [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A, 0x00], // 0x23 #
+    [0x04, 0x0F, 0x14, 0x0E, 0x05, 0x1E, 0x04, 0x00], // 0x24 $
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03, 0x00], // 0x25 %
+    [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D, 0x00], // 0x26 &
+    [0x0C, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x27 '
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02, 0x00], // 0x28 (
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08, 0x00], // 0x29 )
+    [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00, 0x00], // 0x2A *
+    [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00, 0x00], // 0x2B +
+    [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08, 0x00], // 0x2C ,
+    [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00, 0x00], // 0x2D -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00], // 0x2E .
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00, 0x00], // 0x2F /
+    [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E, 0x00], // 0x30 0
+    [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0x31 1
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F, 0x00], // 0x32 2
+    [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E, 0x00], // 0x33 3
+    [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02, 0x00], // 0x34 4
+    [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E, 0x00], // 0x35 5
+    [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E, 0x00], // 0x36 6
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08, 0x00], // 0x37 7
+    [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E, 0x00], // 0x38 8
+    [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C, 0x00], // 0x39 9
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00, 0x00], // 0x3A :
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x04, 0x08, 0x00], // 0x3B ;
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02, 0x00], // 0x3C <
+    [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00, 0x00], // 0x3D =
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08, 0x00], // 0x3E >
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04, 0x00], // 0x3F ?
+    [0x0E, 0x11, 0x01, 0x0D, 0x15, 0x15, 0x0E, 0x00], // 0x40 @
+    [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x00], // 0x41 A
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E, 0x00], // 0x42 B
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E, 0x00], // 0x43 C
+    [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C, 0x00], // 0x44 D
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F, 0x00], // 0x45 E
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10, 0x00], // 0x46 F
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F, 0x00], // 0x47 G
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11, 0x00], // 0x48 H
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0x49 I
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C, 0x00], // 0x4A J
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11, 0x00], // 0x4B K
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F, 0x00], // 0x4C L
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11, 0x00], // 0x4D M
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11, 0x00], // 0x4E N
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0x4F O
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10, 0x00], // 0x50 P
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D, 0x00], // 0x51 Q
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11, 0x00], // 0x52 R
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E, 0x00], // 0x53 S
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00], // 0x54 T
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0x55 U
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04, 0x00], // 0x56 V
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A, 0x00], // 0x57 W
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11, 0x00], // 0x58 X
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04, 0x00], // 0x59 Y
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F, 0x00], // 0x5A Z
+    [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E, 0x00], // 0x5B [
+    [0x11, 0x0A, 0x1F, 0x04, 0x1F, 0x04, 0x04, 0x00], // 0x5C ¥
+    [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E, 0x00], // 0x5D ]
+    [0x04, 0x0A, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x5E ^
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F, 0x00], // 0x5F _
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x60 `
+    [0x00, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00], // 0x61 a
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E, 0x00], // 0x62 b
+    [0x00, 0x00, 0x0E, 0x10, 0x10, 0x11, 0x0E, 0x00], // 0x63 c
+    [0x01, 0x01, 0x0D, 0x13, 0x11, 0x11, 0x0F, 0x00], // 0x64 d
+    [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E, 0x00], // 0x65 e
+    [0x06, 0x09, 0x08, 0x1C, 0x08, 0x08, 0x08, 0x00], // 0x66 f
+    [0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E, 0x00], // 0x67 g
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00], // 0x68 h
+    [0x04, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0x69 i
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0C, 0x00], // 0x6A j
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12, 0x00], // 0x6B k
+    [0x0C, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0x6C l
+    [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11, 0x00], // 0x6D m
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00], // 0x6E n
+    [0x00, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0x6F o
+    [0x00, 0x00, 0x1E, 0x11, 0x1E, 0x10, 0x10, 0x00], // 0x70 p
+    [0x00, 0x00, 0x0D, 0x13, 0x0F, 0x01, 0x01, 0x00], // 0x71 q
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10, 0x00], // 0x72 r
+    [0x00, 0x00, 0x0E, 0x10, 0x0E, 0x01, 0x1E, 0x00], // 0x73 s
+    [0x08, 0x08, 0x1C, 0x08, 0x08, 0x09, 0x06, 0x00], // 0x74 t
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D, 0x00], // 0x75 u
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0A, 0x04, 0x00], // 0x76 v
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0A, 0x00], // 0x77 w
+    [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x00], // 0x78 x
+    [0x00, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E, 0x00], // 0x79 y
+    [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F, 0x00], // 0x7A z
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02, 0x00], // 0x7B {
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00], // 0x7C |
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08, 0x00], // 0x7D }
+    [0x00, 0x04, 0x02, 0x1F, 0x02, 0x04, 0x00, 0x00], // 0x7E →
+    [0x00, 0x04, 0x08, 0x1F, 0x08, 0x04, 0x00, 0x00], // 0x7F ←
+    [0; 8], // 0x80
+    [0; 8], // 0x81
+    [0; 8], // 0x82
+    [0; 8], // 0x83
+    [0; 8], // 0x84
+    [0; 8], // 0x85
+    [0; 8], // 0x86
+    [0; 8], // 0x87
+    [0; 8], // 0x88
+    [0; 8], // 0x89
+    [0; 8], // 0x8A
+    [0; 8], // 0x8B
+    [0; 8], // 0x8C
+    [0; 8], // 0x8D
+    [0; 8], // 0x8E
+    [0; 8], // 0x8F
+    [0; 8], // 0x90
+    [0; 8], // 0x91
+    [0; 8], // 0x92
+    [0; 8], // 0x93
+    [0; 8], // 0x94
+    [0; 8], // 0x95
+    [0; 8], // 0x96
+    [0; 8], // 0x97
+    [0; 8], // 0x98
+    [0; 8], // 0x99
+    [0; 8], // 0x9A
+    [0; 8], // 0x9B
+    [0; 8], // 0x9C
+    [0; 8], // 0x9D
+    [0; 8], // 0x9E
+    [0; 8], // 0x9F
+    [0; 8], // 0xA0
+    [0x00, 0x00, 0x00, 0x00, 0x1C, 0x14, 0x1C, 0x00], // 0xA1 ｡
+    [0x07, 0x04, 0x04, 0x04, 0x00, 0x00, 0x00, 0x00], // 0xA2 ｢
+    [0x00, 0x00, 0x00, 0x04, 0x04, 0x04, 0x1C, 0x00], // 0xA3 ｣
+    [0x00, 0x00, 0x00, 0x00, 0x10, 0x08, 0x04, 0x00], // 0xA4 ､
+    [0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00, 0x00, 0x00], // 0xA5 ･
+    [0x00, 0x1F, 0x01, 0x1F, 0x01, 0x02, 0x04, 0x00], // 0xA6 ｦ
+    [0x00, 0x00, 0x1F, 0x01, 0x06, 0x04, 0x08, 0x00], // 0xA7 ｧ
+    [0x00, 0x00, 0x02, 0x04, 0x0C, 0x14, 0x04, 0x00], // 0xA8 ｨ
+    [0x00, 0x00, 0x04, 0x1F, 0x11, 0x01, 0x06, 0x00], // 0xA9 ｩ
+    [0x00, 0x00, 0x00, 0x1F, 0x04, 0x04, 0x1F, 0x00], // 0xAA ｪ
+    [0x00, 0x00, 0x02, 0x1F, 0x06, 0x0A, 0x12, 0x00], // 0xAB ｫ
+    [0x00, 0x00, 0x08, 0x1F, 0x09, 0x0A, 0x08, 0x00], // 0xAC ｬ
+    [0x00, 0x00, 0x00, 0x0E, 0x02, 0x02, 0x1F, 0x00], // 0xAD ｭ
+    [0x00, 0x00, 0x1E, 0x02, 0x1E, 0x02, 0x1E, 0x00], // 0xAE ｮ
+    [0x00, 0x00, 0x00, 0x15, 0x15, 0x01, 0x06, 0x00], // 0xAF ｯ
+    [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00, 0x00], // 0xB0 ｰ
+    [0x1F, 0x01, 0x05, 0x06, 0x04, 0x04, 0x08, 0x00], // 0xB1 ｱ
+    [0x01, 0x02, 0x04, 0x0C, 0x14, 0x04, 0x04, 0x00], // 0xB2 ｲ
+    [0x04, 0x1F, 0x11, 0x11, 0x01, 0x02, 0x04, 0x00], // 0xB3 ｳ
+    [0x00, 0x1F, 0x04, 0x04, 0x04, 0x04, 0x1F, 0x00], // 0xB4 ｴ
+    [0x02, 0x1F, 0x02, 0x06, 0x0A, 0x12, 0x02, 0x00], // 0xB5 ｵ
+    [0x08, 0x1F, 0x09, 0x09, 0x09, 0x09, 0x12, 0x00], // 0xB6 ｶ
+    [0x04, 0x1F, 0x04, 0x1F, 0x04, 0x04, 0x04, 0x00], // 0xB7 ｷ
+    [0x00, 0x0F, 0x09, 0x11, 0x01, 0x02, 0x0C, 0x00], // 0xB8 ｸ
+    [0x08, 0x0F, 0x12, 0x02, 0x02, 0x02, 0x04, 0x00], // 0xB9 ｹ
+    [0x00, 0x1F, 0x01, 0x01, 0x01, 0x01, 0x1F, 0x00], // 0xBA ｺ
+    [0x0A, 0x1F, 0x0A, 0x0A, 0x02, 0x04, 0x08, 0x00], // 0xBB ｻ
+    [0x00, 0x18, 0x01, 0x19, 0x01, 0x02, 0x1C, 0x00], // 0xBC ｼ
+    [0x00, 0x1F, 0x01, 0x02, 0x04, 0x0A, 0x11, 0x00], // 0xBD ｽ
+    [0x08, 0x1F, 0x09, 0x0A, 0x08, 0x08, 0x07, 0x00], // 0xBE ｾ
+    [0x00, 0x11, 0x11, 0x09, 0x01, 0x02, 0x0C, 0x00], // 0xBF ｿ
+    [0x00, 0x0F, 0x09, 0x15, 0x03, 0x02, 0x0C, 0x00], // 0xC0 ﾀ
+    [0x02, 0x1C, 0x04, 0x1F, 0x04, 0x04, 0x08, 0x00], // 0xC1 ﾁ
+    [0x00, 0x15, 0x15, 0x15, 0x01, 0x02, 0x04, 0x00], // 0xC2 ﾂ
+    [0x0E, 0x00, 0x1F, 0x04, 0x04, 0x04, 0x08, 0x00], // 0xC3 ﾃ
+    [0x08, 0x08, 0x08, 0x0C, 0x0A, 0x08, 0x08, 0x00], // 0xC4 ﾄ
+    [0x04, 0x04, 0x1F, 0x04, 0x04, 0x08, 0x10, 0x00], // 0xC5 ﾅ
+    [0x00, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x1F, 0x00], // 0xC6 ﾆ
+    [0x00, 0x1F, 0x01, 0x0A, 0x04, 0x0A, 0x10, 0x00], // 0xC7 ﾇ
+    [0x04, 0x1F, 0x02, 0x04, 0x0E, 0x15, 0x04, 0x00], // 0xC8 ﾈ
+    [0x02, 0x02, 0x02, 0x02, 0x02, 0x04, 0x08, 0x00], // 0xC9 ﾉ
+    [0x00, 0x04, 0x02, 0x11, 0x11, 0x11, 0x11, 0x00], // 0xCA ﾊ
+    [0x10, 0x10, 0x1F, 0x10, 0x10, 0x10, 0x0F, 0x00], // 0xCB ﾋ
+    [0x00, 0x1F, 0x01, 0x01, 0x01, 0x02, 0x0C, 0x00], // 0xCC ﾌ
+    [0x00, 0x08, 0x14, 0x02, 0x01, 0x01, 0x00, 0x00], // 0xCD ﾍ
+    [0x04, 0x1F, 0x04, 0x04, 0x15, 0x15, 0x04, 0x00], // 0xCE ﾎ
+    [0x00, 0x1F, 0x01, 0x01, 0x0A, 0x04, 0x02, 0x00], // 0xCF ﾏ
+    [0x00, 0x0E, 0x00, 0x0E, 0x00, 0x0E, 0x01, 0x00], // 0xD0 ﾐ
+    [0x00, 0x04, 0x08, 0x10, 0x11, 0x1F, 0x01, 0x00], // 0xD1 ﾑ
+    [0x00, 0x01, 0x01, 0x0A, 0x04, 0x0A, 0x10, 0x00], // 0xD2 ﾒ
+    [0x00, 0x1F, 0x08, 0x1F, 0x08, 0x08, 0x07, 0x00], // 0xD3 ﾓ
+    [0x08, 0x08, 0x1F, 0x09, 0x0A, 0x08, 0x08, 0x00], // 0xD4 ﾔ
+    [0x00, 0x0E, 0x02, 0x02, 0x02, 0x02, 0x1F, 0x00], // 0xD5 ﾕ
+    [0x00, 0x1F, 0x01, 0x1F, 0x01, 0x01, 0x1F, 0x00], // 0xD6 ﾖ
+    [0x0E, 0x00, 0x1F, 0x01, 0x01, 0x02, 0x04, 0x00], // 0xD7 ﾗ
+    [0x12, 0x12, 0x12, 0x12, 0x02, 0x04, 0x08, 0x00], // 0xD8 ﾘ
+    [0x00, 0x04, 0x14, 0x14, 0x15, 0x15, 0x16, 0x00], // 0xD9 ﾙ
+    [0x00, 0x10, 0x10, 0x11, 0x12, 0x14, 0x18, 0x00], // 0xDA ﾚ
+    [0x00, 0x1F, 0x11, 0x11, 0x11, 0x11, 0x1F, 0x00], // 0xDB ﾛ
+    [0x00, 0x1F, 0x11, 0x11, 0x01, 0x02, 0x04, 0x00], // 0xDC ﾜ
+    [0x00, 0x18, 0x00, 0x01, 0x01, 0x02, 0x1C, 0x00], // 0xDD ﾝ
+    [0x04, 0x12, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xDE ﾞ
+    [0x1C, 0x14, 0x1C, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xDF ﾟ
+    [0x00, 0x00, 0x09, 0x15, 0x12, 0x12, 0x0D, 0x00], // 0xE0 α
+    [0x0A, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00], // 0xE1 ä
+    [0x00, 0x0E, 0x11, 0x1E, 0x11, 0x1E, 0x10, 0x00], // 0xE2 β
+    [0x00, 0x00, 0x0E, 0x10, 0x0C, 0x11, 0x0E, 0x00], // 0xE3 ε
+    [0x00, 0x11, 0x11, 0x11, 0x1D, 0x10, 0x10, 0x00], // 0xE4 μ
+    [0x00, 0x00, 0x0F, 0x14, 0x12, 0x11, 0x0E, 0x00], // 0xE5 σ
+    [0x00, 0x06, 0x09, 0x11, 0x1E, 0x10, 0x10, 0x00], // 0xE6 ρ
+    [0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E, 0x00], // 0xE7 g
+    [0x00, 0x00, 0x07, 0x04, 0x04, 0x14, 0x08, 0x00], // 0xE8 √
+    [0x00, 0x02, 0x1A, 0x02, 0x00, 0x00, 0x00, 0x00], // 0xE9 ⁻¹
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0C, 0x00], // 0xEA j
+    [0x00, 0x14, 0x08, 0x14, 0x00, 0x00, 0x00, 0x00], // 0xEB ˣ
+    [0x00, 0x04, 0x0E, 0x14, 0x15, 0x0E, 0x04, 0x00], // 0xEC ¢
+    [0x08, 0x08, 0x1C, 0x08, 0x1C, 0x08, 0x0F, 0x00], // 0xED £
+    [0x0E, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00], // 0xEE ñ
+    [0x0A, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xEF ö
+    [0x00, 0x00, 0x1E, 0x11, 0x1E, 0x10, 0x10, 0x00], // 0xF0 p
+    [0x00, 0x00, 0x0D, 0x13, 0x0F, 0x01, 0x01, 0x00], // 0xF1 q
+    [0x00, 0x0E, 0x11, 0x1F, 0x11, 0x11, 0x0E, 0x00], // 0xF2 θ
+    [0x00, 0x00, 0x00, 0x0B, 0x15, 0x1A, 0x00, 0x00], // 0xF3 ∞
+    [0x00, 0x00, 0x0E, 0x11, 0x11, 0x0A, 0x1B, 0x00], // 0xF4 Ω
+    [0x0A, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D, 0x00], // 0xF5 ü
+    [0x1F, 0x10, 0x08, 0x04, 0x08, 0x10, 0x1F, 0x00], // 0xF6 Σ
+    [0x00, 0x00, 0x1F, 0x0A, 0x0A, 0x0A, 0x13, 0x00], // 0xF7 π
+    [0x1F, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x00], // 0xF8 x̄
+    [0x00, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E, 0x00], // 0xF9 y
+    [0x00, 0x01, 0x1E, 0x04, 0x1F, 0x04, 0x04, 0x00], // 0xFA 千
+    [0x00, 0x00, 0x1F, 0x08, 0x0F, 0x09, 0x11, 0x00], // 0xFB 万
+    [0x00, 0x00, 0x1F, 0x15, 0x1F, 0x11, 0x11, 0x00], // 0xFC 円
+    [0x00, 0x04, 0x00, 0x1F, 0x00, 0x04, 0x00, 0x00], // 0xFD ÷
+    [0; 8], // 0xFE
+    [0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x1F], // 0xFF █
+];
+
+/// ### 5x10 glyphs of the A00 ROM from 0xE0
+#[rustfmt::skip]
+pub const ROM_A00_5X10: [[u8; 11]; 32] = [
+    [0x00, 0x00, 0x09, 0x15, 0x12, 0x12, 0x0D, 0x00, 0x00, 0x00, 0x00], // 0xE0 α
+    [0x0A, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00, 0x00, 0x00, 0x00], // 0xE1 ä
+    [0x00, 0x00, 0x0E, 0x11, 0x1E, 0x11, 0x1E, 0x10, 0x10, 0x10, 0x00], // 0xE2 β
+    [0x00, 0x00, 0x0E, 0x10, 0x0C, 0x11, 0x0E, 0x00, 0x00, 0x00, 0x00], // 0xE3 ε
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x1D, 0x10, 0x10, 0x10, 0x00], // 0xE4 μ
+    [0x00, 0x00, 0x0F, 0x14, 0x12, 0x11, 0x0E, 0x00, 0x00, 0x00, 0x00], // 0xE5 σ
+    [0x00, 0x00, 0x06, 0x09, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10, 0x00], // 0xE6 ρ
+    [0x00, 0x00, 0x0F, 0x11, 0x11, 0x11, 0x0F, 0x01, 0x01, 0x0E, 0x00], // 0xE7 g
+    [0x00, 0x00, 0x07, 0x04, 0x04, 0x14, 0x08, 0x00, 0x00, 0x00, 0x00], // 0xE8 √
+    [0x00, 0x02, 0x1A, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xE9 ⁻¹
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C, 0x00], // 0xEA j
+    [0x00, 0x14, 0x08, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xEB ˣ
+    [0x00, 0x04, 0x0E, 0x14, 0x15, 0x0E, 0x04, 0x00, 0x00, 0x00, 0x00], // 0xEC ¢
+    [0x08, 0x08, 0x1C, 0x08, 0x1C, 0x08, 0x0F, 0x00, 0x00, 0x00, 0x00], // 0xED £
+    [0x0E, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00, 0x00, 0x00, 0x00], // 0xEE ñ
+    [0x0A, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00, 0x00, 0x00, 0x00], // 0xEF ö
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10, 0x00], // 0xF0 p
+    [0x00, 0x00, 0x0D, 0x13, 0x11, 0x11, 0x0F, 0x01, 0x01, 0x01, 0x00], // 0xF1 q
+    [0x00, 0x0E, 0x11, 0x1F, 0x11, 0x11, 0x0E, 0x00, 0x00, 0x00, 0x00], // 0xF2 θ
+    [0x00, 0x00, 0x00, 0x0B, 0x15, 0x1A, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xF3 ∞
+    [0x00, 0x00, 0x0E, 0x11, 0x11, 0x0A, 0x1B, 0x00, 0x00, 0x00, 0x00], // 0xF4 Ω
+    [0x0A, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D, 0x00, 0x00, 0x00, 0x00], // 0xF5 ü
+    [0x1F, 0x10, 0x08, 0x04, 0x08, 0x10, 0x1F, 0x00, 0x00, 0x00, 0x00], // 0xF6 Σ
+    [0x00, 0x00, 0x1F, 0x0A, 0x0A, 0x0A, 0x13, 0x00, 0x00, 0x00, 0x00], // 0xF7 π
+    [0x1F, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x00, 0x00, 0x00, 0x00], // 0xF8 x̄
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x11, 0x0F, 0x01, 0x01, 0x0E, 0x00], // 0xF9 y
+    [0x00, 0x01, 0x1E, 0x04, 0x1F, 0x04, 0x04, 0x00, 0x00, 0x00, 0x00], // 0xFA 千
+    [0x00, 0x00, 0x1F, 0x08, 0x0F, 0x09, 0x11, 0x00, 0x00, 0x00, 0x00], // 0xFB 万
+    [0x00, 0x00, 0x1F, 0x15, 0x1F, 0x11, 0x11, 0x00, 0x00, 0x00, 0x00], // 0xFC 円
+    [0x00, 0x04, 0x00, 0x1F, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xFD ÷
+    [0; 11], // 0xFE
+    [0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x00], // 0xFF █
+];
+
+/// ### 5x8 glyphs of the A02 ROM
+/// 0x00 - 0x0F are the CGRAM
+#[rustfmt::skip]
+pub const ROM_A02: [[u8; 8]; 256] = [
+    [0; 8], // 0x00
+    [0; 8], // 0x01
+    [0; 8], // 0x02
+    [0; 8], // 0x03
+    [0; 8], // 0x04
+    [0; 8], // 0x05
+    [0; 8], // 0x06
+    [0; 8], // 0x07
+    [0; 8], // 0x08
+    [0; 8], // 0x09
+    [0; 8], // 0x0A
+    [0; 8], // 0x0B
+    [0; 8], // 0x0C
+    [0; 8], // 0x0D
+    [0; 8], // 0x0E
+    [0; 8], // 0x0F
+    [0x08, 0x0C, 0x0E, 0x0F, 0x0E, 0x0C, 0x08, 0x00], // 0x10 ▶
+    [0x02, 0x06, 0x0E, 0x1E, 0x0E, 0x06, 0x02, 0x00], // 0x11 ◀
+    [0x09, 0x12, 0x1B, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x12 “
+    [0x1B, 0x09, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x13 ”
+    [0x04, 0x0E, 0x1F, 0x00, 0x04, 0x0E, 0x1F, 0x00], // 0x14 ⏫
+    [0x1F, 0x0E, 0x04, 0x00, 0x1F, 0x0E, 0x04, 0x00], // 0x15 ⏬
+    [0x00, 0x0E, 0x1F, 0x1F, 0x1F, 0x0E, 0x00, 0x00], // 0x16 ●
+    [0x01, 0x01, 0x05, 0x09, 0x1F, 0x08, 0x04, 0x00], // 0x17 ↲
+    [0x04, 0x0E, 0x15, 0x04, 0x04, 0x04, 0x04, 0x00], // 0x18 ↑
+    [0x04, 0x04, 0x04, 0x04, 0x15, 0x0E, 0x04, 0x00], // 0x19 ↓
+    [0x00, 0x04, 0x02, 0x1F, 0x02, 0x04, 0x00, 0x00], // 0x1A →
+    [0x00, 0x04, 0x08, 0x1F, 0x08, 0x04, 0x00, 0x00], // 0x1B ←
+    [0x02, 0x04, 0x08, 0x04, 0x02, 0x00, 0x1F, 0x00], // 0x1C ≤
+    [0x08, 0x04, 0x02, 0x04, 0x08, 0x00, 0x1F, 0x00], // 0x1D ≥
+    [0x00, 0x04, 0x04, 0x0E, 0x0E, 0x1F, 0x00, 0x00], // 0x1E ▲
+    [0x00, 0x1F, 0x0E, 0x0E, 0x04, 0x04, 0x00, 0x00], // 0x1F ▼
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x20 space
+    [0x04, 0x04, 0x04, 0x04, 0x00, 0x00, 0x04, 0x00], // 0x21 !
+    [0x0A, 0x0A, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x22 "
+    [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A, 0x00], // 0x23 #
+    [0x04, 0x0F, 0x14, 0x0E, 0x05, 0x1E, 0x04, 0x00], // 0x24 $
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03, 0x00], // 0x25 %
+    [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D, 0x00], // 0x26 &
+    [0x0C, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x27 '
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02, 0x00], // 0x28 (
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08, 0x00], // 0x29 )
+    [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00, 0x00], // 0x2A *
+    [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00, 0x00], // 0x2B +
+    [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08, 0x00], // 0x2C ,
+    [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00, 0x00], // 0x2D -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00], // 0x2E .
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00, 0x00], // 0x2F /
+    [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E, 0x00], // 0x30 0
+    [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0x31 1
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F, 0x00], // 0x32 2
+    [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E, 0x00], // 0x33 3
+    [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02, 0x00], // 0x34 4
+    [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E, 0x00], // 0x35 5
+    [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E, 0x00], // 0x36 6
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08, 0x00], // 0x37 7
+    [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E, 0x00], // 0x38 8
+    [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C, 0x00], // 0x39 9
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00, 0x00], // 0x3A :
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x04, 0x08, 0x00], // 0x3B ;
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02, 0x00], // 0x3C <
+    [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00, 0x00], // 0x3D =
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08, 0x00], // 0x3E >
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04, 0x00], // 0x3F ?
+    [0x0E, 0x11, 0x01, 0x0D, 0x15, 0x15, 0x0E, 0x00], // 0x40 @
+    [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x00], // 0x41 A
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E, 0x00], // 0x42 B
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E, 0x00], // 0x43 C
+    [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C, 0x00], // 0x44 D
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F, 0x00], // 0x45 E
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10, 0x00], // 0x46 F
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F, 0x00], // 0x47 G
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11, 0x00], // 0x48 H
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0x49 I
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C, 0x00], // 0x4A J
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11, 0x00], // 0x4B K
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F, 0x00], // 0x4C L
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11, 0x00], // 0x4D M
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11, 0x00], // 0x4E N
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0x4F O
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10, 0x00], // 0x50 P
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D, 0x00], // 0x51 Q
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11, 0x00], // 0x52 R
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E, 0x00], // 0x53 S
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00], // 0x54 T
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0x55 U
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04, 0x00], // 0x56 V
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A, 0x00], // 0x57 W
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11, 0x00], // 0x58 X
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04, 0x00], // 0x59 Y
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F, 0x00], // 0x5A Z
+    [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E, 0x00], // 0x5B [
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00, 0x00], // 0x5C \
+    [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E, 0x00], // 0x5D ]
+    [0x04, 0x0A, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x5E ^
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F, 0x00], // 0x5F _
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x60 `
+    [0x00, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00], // 0x61 a
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E, 0x00], // 0x62 b
+    [0x00, 0x00, 0x0E, 0x10, 0x10, 0x11, 0x0E, 0x00], // 0x63 c
+    [0x01, 0x01, 0x0D, 0x13, 0x11, 0x11, 0x0F, 0x00], // 0x64 d
+    [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E, 0x00], // 0x65 e
+    [0x06, 0x09, 0x08, 0x1C, 0x08, 0x08, 0x08, 0x00], // 0x66 f
+    [0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E, 0x00], // 0x67 g
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00], // 0x68 h
+    [0x04, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0x69 i
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0C, 0x00], // 0x6A j
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12, 0x00], // 0x6B k
+    [0x0C, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0x6C l
+    [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11, 0x00], // 0x6D m
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00], // 0x6E n
+    [0x00, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0x6F o
+    [0x00, 0x00, 0x1E, 0x11, 0x1E, 0x10, 0x10, 0x00], // 0x70 p
+    [0x00, 0x00, 0x0D, 0x13, 0x0F, 0x01, 0x01, 0x00], // 0x71 q
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10, 0x00], // 0x72 r
+    [0x00, 0x00, 0x0E, 0x10, 0x0E, 0x01, 0x1E, 0x00], // 0x73 s
+    [0x08, 0x08, 0x1C, 0x08, 0x08, 0x09, 0x06, 0x00], // 0x74 t
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D, 0x00], // 0x75 u
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0A, 0x04, 0x00], // 0x76 v
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0A, 0x00], // 0x77 w
+    [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x00], // 0x78 x
+    [0x00, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E, 0x00], // 0x79 y
+    [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F, 0x00], // 0x7A z
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02, 0x00], // 0x7B {
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00], // 0x7C |
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08, 0x00], // 0x7D }
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00, 0x00], // 0x7E ~
+    [0x04, 0x0A, 0x11, 0x11, 0x11, 0x1F, 0x00, 0x00], // 0x7F ⌂
+    [0x1F, 0x10, 0x10, 0x1E, 0x11, 0x11, 0x1E, 0x00], // 0x80 Б
+    [0x06, 0x0A, 0x0A, 0x0A, 0x0A, 0x1F, 0x11, 0x00], // 0x81 Д
+    [0x15, 0x15, 0x15, 0x0E, 0x15, 0x15, 0x15, 0x00], // 0x82 Ж
+    [0x1E, 0x01, 0x01, 0x0E, 0x01, 0x01, 0x1E, 0x00], // 0x83 З
+    [0x11, 0x11, 0x13, 0x15, 0x19, 0x11, 0x11, 0x00], // 0x84 И
+    [0x0A, 0x04, 0x11, 0x13, 0x15, 0x19, 0x11, 0x00], // 0x85 Й
+    [0x07, 0x09, 0x09, 0x09, 0x09, 0x09, 0x11, 0x00], // 0x86 Л
+    [0x1F, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x00], // 0x87 П
+    [0x11, 0x11, 0x11, 0x0F, 0x01, 0x11, 0x0E, 0x00], // 0x88 У
+    [0x12, 0x12, 0x12, 0x12, 0x12, 0x1F, 0x01, 0x00], // 0x89 Ц
+    [0x11, 0x11, 0x11, 0x0F, 0x01, 0x01, 0x01, 0x00], // 0x8A Ч
+    [0x15, 0x15, 0x15, 0x15, 0x15, 0x15, 0x1F, 0x00], // 0x8B Ш
+    [0x15, 0x15, 0x15, 0x15, 0x15, 0x1F, 0x01, 0x00], // 0x8C Щ
+    [0x18, 0x08, 0x08, 0x0E, 0x09, 0x09, 0x0E, 0x00], // 0x8D Ъ
+    [0x11, 0x11, 0x11, 0x1D, 0x13, 0x13, 0x1D, 0x00], // 0x8E Ы
+    [0x0E, 0x11, 0x01, 0x07, 0x01, 0x11, 0x0E, 0x00], // 0x8F Э
+    [0x00, 0x00, 0x09, 0x15, 0x12, 0x12, 0x0D, 0x00], // 0x90 α
+    [0x04, 0x06, 0x05, 0x04, 0x0C, 0x1C, 0x18, 0x00], // 0x91 ♪
+    [0x1F, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00], // 0x92 Γ
+    [0x00, 0x00, 0x1F, 0x0A, 0x0A, 0x0A, 0x13, 0x00], // 0x93 π
+    [0x1F, 0x10, 0x08, 0x04, 0x08, 0x10, 0x1F, 0x00], // 0x94 Σ
+    [0x00, 0x00, 0x0F, 0x12, 0x12, 0x12, 0x0C, 0x00], // 0x95 σ
+    [0x03, 0x05, 0x07, 0x05, 0x0D, 0x1B, 0x18, 0x00], // 0x96 ♬
+    [0x00, 0x01, 0x0E, 0x14, 0x04, 0x04, 0x02, 0x00], // 0x97 τ
+    [0x04, 0x0E, 0x0E, 0x0E, 0x1F, 0x00, 0x04, 0x00], // 0x98 🔔
+    [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x0E, 0x00], // 0x99 Θ
+    [0x00, 0x0E, 0x11, 0x11, 0x11, 0x0A, 0x1B, 0x00], // 0x9A Ω
+    [0x06, 0x09, 0x04, 0x0A, 0x11, 0x11, 0x0E, 0x00], // 0x9B δ
+    [0x00, 0x00, 0x0A, 0x15, 0x15, 0x0A, 0x00, 0x00], // 0x9C ∞
+    [0x00, 0x0A, 0x1F, 0x1F, 0x0E, 0x04, 0x00, 0x00], // 0x9D ♥
+    [0x00, 0x00, 0x0F, 0x10, 0x0E, 0x10, 0x0F, 0x00], // 0x9E ε
+    [0x00, 0x0E, 0x11, 0x11, 0x11, 0x11, 0x00, 0x00], // 0x9F ∩
+    [0; 8], // 0xA0
+    [0x04, 0x00, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00], // 0xA1 ¡
+    [0x04, 0x0E, 0x14, 0x14, 0x15, 0x0E, 0x04, 0x00], // 0xA2 ¢
+    [0x06, 0x09, 0x08, 0x1E, 0x08, 0x09, 0x16, 0x00], // 0xA3 £
+    [0x11, 0x0E, 0x0A, 0x0A, 0x0E, 0x11, 0x00, 0x00], // 0xA4 ¤
+    [0x11, 0x0A, 0x1F, 0x04, 0x1F, 0x04, 0x04, 0x00], // 0xA5 ¥
+    [0x04, 0x04, 0x04, 0x00, 0x04, 0x04, 0x04, 0x00], // 0xA6 ¦
+    [0x06, 0x09, 0x04, 0x0A, 0x04, 0x12, 0x0C, 0x00], // 0xA7 §
+    [0x03, 0x04, 0x04, 0x0E, 0x04, 0x04, 0x18, 0x00], // 0xA8 ƒ
+    [0x0E, 0x11, 0x17, 0x15, 0x17, 0x11, 0x0E, 0x00], // 0xA9 ©
+    [0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00, 0x1F, 0x00], // 0xAA ª
+    [0x00, 0x05, 0x0A, 0x14, 0x0A, 0x05, 0x00, 0x00], // 0xAB «
+    [0x12, 0x15, 0x15, 0x1D, 0x15, 0x15, 0x12, 0x00], // 0xAC Ю
+    [0x0F, 0x11, 0x11, 0x0F, 0x05, 0x09, 0x11, 0x00], // 0xAD Я
+    [0x0E, 0x11, 0x1D, 0x1B, 0x1D, 0x1B, 0x0E, 0x00], // 0xAE ®
+    [0x04, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xAF '
+    [0x06, 0x09, 0x09, 0x06, 0x00, 0x00, 0x00, 0x00], // 0xB0 °
+    [0x04, 0x04, 0x1F, 0x04, 0x04, 0x00, 0x1F, 0x00], // 0xB1 ±
+    [0x0C, 0x02, 0x04, 0x0E, 0x00, 0x00, 0x00, 0x00], // 0xB2 ²
+    [0x0E, 0x02, 0x06, 0x02, 0x0E, 0x00, 0x00, 0x00], // 0xB3 ³
+    [0x1C, 0x14, 0x1C, 0x12, 0x17, 0x12, 0x13, 0x00], // 0xB4 ₧
+    [0x00, 0x11, 0x11, 0x11, 0x13, 0x1D, 0x10, 0x00], // 0xB5 μ
+    [0x0F, 0x13, 0x13, 0x0F, 0x03, 0x03, 0x03, 0x00], // 0xB6 ¶
+    [0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00, 0x00, 0x00], // 0xB7 ·
+    [0x00, 0x00, 0x00, 0x0A, 0x11, 0x15, 0x0A, 0x00], // 0xB8 ω
+    [0x04, 0x0C, 0x04, 0x0E, 0x00, 0x00, 0x00, 0x00], // 0xB9 ¹
+    [0x0E, 0x11, 0x11, 0x0E, 0x00, 0x1F, 0x00, 0x00], // 0xBA º
+    [0x00, 0x14, 0x0A, 0x05, 0x0A, 0x14, 0x00, 0x00], // 0xBB »
+    [0x10, 0x12, 0x14, 0x0B, 0x15, 0x07, 0x01, 0x00], // 0xBC ¼
+    [0x10, 0x12, 0x14, 0x0E, 0x11, 0x02, 0x07, 0x00], // 0xBD ½
+    [0x18, 0x09, 0x1A, 0x05, 0x0B, 0x17, 0x01, 0x00], // 0xBE ¾
+    [0x04, 0x00, 0x04, 0x08, 0x10, 0x11, 0x0E, 0x00], // 0xBF ¿
+    [0x08, 0x04, 0x0E, 0x11, 0x1F, 0x11, 0x11, 0x00], // 0xC0 À
+    [0x02, 0x04, 0x0E, 0x11, 0x1F, 0x11, 0x11, 0x00], // 0xC1 Á
+    [0x04, 0x0A, 0x0E, 0x11, 0x1F, 0x11, 0x11, 0x00], // 0xC2 Â
+    [0x0D, 0x16, 0x0E, 0x11, 0x1F, 0x11, 0x11, 0x00], // 0xC3 Ã
+    [0x0A, 0x00, 0x0E, 0x11, 0x1F, 0x11, 0x11, 0x00], // 0xC4 Ä
+    [0x04, 0x0A, 0x04, 0x0E, 0x11, 0x1F, 0x11, 0x00], // 0xC5 Å
+    [0x0F, 0x14, 0x14, 0x1E, 0x14, 0x14, 0x17, 0x00], // 0xC6 Æ
+    [0x0E, 0x10, 0x10, 0x10, 0x0E, 0x04, 0x0C, 0x00], // 0xC7 Ç
+    [0x08, 0x04, 0x1F, 0x10, 0x1E, 0x10, 0x1F, 0x00], // 0xC8 È
+    [0x02, 0x04, 0x1F, 0x10, 0x1E, 0x10, 0x1F, 0x00], // 0xC9 É
+    [0x04, 0x0A, 0x1F, 0x10, 0x1E, 0x10, 0x1F, 0x00], // 0xCA Ê
+    [0x0A, 0x00, 0x1F, 0x10, 0x1E, 0x10, 0x1F, 0x00], // 0xCB Ë
+    [0x08, 0x04, 0x0E, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0xCC Ì
+    [0x02, 0x04, 0x0E, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0xCD Í
+    [0x04, 0x0A, 0x0E, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0xCE Î
+    [0x0A, 0x00, 0x0E, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0xCF Ï
+    [0x0E, 0x09, 0x09, 0x1D, 0x09, 0x09, 0x0E, 0x00], // 0xD0 Ð
+    [0x0D, 0x16, 0x11, 0x19, 0x15, 0x13, 0x11, 0x00], // 0xD1 Ñ
+    [0x08, 0x04, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xD2 Ò
+    [0x02, 0x04, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xD3 Ó
+    [0x04, 0x0A, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xD4 Ô
+    [0x0D, 0x16, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xD5 Õ
+    [0x0A, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xD6 Ö
+    [0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x00, 0x00], // 0xD7 ×
+    [0x0E, 0x13, 0x15, 0x15, 0x15, 0x19, 0x0E, 0x00], // 0xD8 Ø
+    [0x08, 0x04, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xD9 Ù
+    [0x02, 0x04, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xDA Ú
+    [0x04, 0x0A, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xDB Û
+    [0x0A, 0x00, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xDC Ü
+    [0x02, 0x04, 0x11, 0x0A, 0x04, 0x04, 0x04, 0x00], // 0xDD Ý
+    [0x10, 0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x00], // 0xDE Þ
+    [0x0C, 0x12, 0x12, 0x14, 0x12, 0x11, 0x16, 0x00], // 0xDF ß
+    [0x08, 0x04, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00], // 0xE0 à
+    [0x02, 0x04, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00], // 0xE1 á
+    [0x04, 0x0A, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00], // 0xE2 â
+    [0x0D, 0x16, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00], // 0xE3 ã
+    [0x0A, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00], // 0xE4 ä
+    [0x04, 0x0A, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00], // 0xE5 å
+    [0x00, 0x00, 0x1A, 0x05, 0x0F, 0x14, 0x0F, 0x00], // 0xE6 æ
+    [0x00, 0x0E, 0x10, 0x11, 0x0E, 0x04, 0x0C, 0x00], // 0xE7 ç
+    [0x08, 0x04, 0x0E, 0x11, 0x1F, 0x10, 0x0E, 0x00], // 0xE8 è
+    [0x02, 0x04, 0x0E, 0x11, 0x1F, 0x10, 0x0E, 0x00], // 0xE9 é
+    [0x04, 0x0A, 0x0E, 0x11, 0x1F, 0x10, 0x0E, 0x00], // 0xEA ê
+    [0x0A, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E, 0x00], // 0xEB ë
+    [0x08, 0x04, 0x0C, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0xEC ì
+    [0x02, 0x04, 0x0C, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0xED í
+    [0x04, 0x0A, 0x0C, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0xEE î
+    [0x0A, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E, 0x00], // 0xEF ï
+    [0x0A, 0x04, 0x0A, 0x0E, 0x11, 0x11, 0x0E, 0x00], // 0xF0 ð
+    [0x0D, 0x16, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00], // 0xF1 ñ
+    [0x08, 0x04, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xF2 ò
+    [0x02, 0x04, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xF3 ó
+    [0x04, 0x0A, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xF4 ô
+    [0x0D, 0x16, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xF5 õ
+    [0x0A, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00], // 0xF6 ö
+    [0x00, 0x04, 0x00, 0x1F, 0x00, 0x04, 0x00, 0x00], // 0xF7 ÷
+    [0x00, 0x01, 0x0E, 0x15, 0x15, 0x0E, 0x10, 0x00], // 0xF8 ø
+    [0x08, 0x04, 0x11, 0x11, 0x11, 0x13, 0x0D, 0x00], // 0xF9 ù
+    [0x02, 0x04, 0x11, 0x11, 0x11, 0x13, 0x0D, 0x00], // 0xFA ú
+    [0x04, 0x0A, 0x11, 0x11, 0x11, 0x13, 0x0D, 0x00], // 0xFB û
+    [0x0A, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D, 0x00], // 0xFC ü
+    [0x02, 0x04, 0x11, 0x11, 0x0F, 0x01, 0x0E, 0x00], // 0xFD ý
+    [0x00, 0x10, 0x1E, 0x11, 0x1E, 0x10, 0x10, 0x00], // 0xFE þ
+    [0x0A, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E, 0x00], // 0xFF ÿ
+];
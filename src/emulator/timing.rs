@@ -0,0 +1,494 @@
+use std::cell::Cell;
+use std::fmt;
+use std::rc::Rc;
+
+use super::decoder::{BusDecoder, Operation, Transaction};
+#[cfg(feature = "async")]
+use crate::lcd_trait::ADelay;
+use crate::lcd_trait::{DelayNs, Interface, InterfaceKind, ReadInterface, _RW, EN, RS};
+
+/// ### virtual time in ns
+/// the clones share the same time, give one to `VirtualDelay` and one to `TimingChecker`
+#[derive(Clone, Default)]
+pub struct VirtualClock(Rc<Cell<u64>>);
+
+impl VirtualClock {
+    /// ### clock at 0 ns (power on)
+    pub fn new() -> VirtualClock {
+        VirtualClock::default()
+    }
+
+    /// ### time since power on in ns
+    pub fn now(&self) -> u64 {
+        self.0.get()
+    }
+
+    pub fn advance(&self, ns: u64) {
+        self.0.set(self.0.get() + ns);
+    }
+}
+
+/// ### `DelayNs` that advances a `VirtualClock` instead of waiting
+#[derive(Clone, Default)]
+pub struct VirtualDelay {
+    clock: VirtualClock,
+}
+
+impl VirtualDelay {
+    pub fn new(clock: VirtualClock) -> VirtualDelay {
+        VirtualDelay { clock }
+    }
+
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+}
+
+impl DelayNs for VirtualDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.clock.advance(ns as u64);
+    }
+}
+
+#[cfg(feature = "async")]
+impl ADelay for VirtualDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.clock.advance(ns as u64);
+    }
+}
+
+/// ### minimum times in ns
+#[derive(Clone, Copy, Debug)]
+pub struct Timings {
+    /// from power on to the first instruction
+    pub power_on: u64,
+    /// after the first function set of the reset sequence
+    pub reset_first: u64,
+    /// after the second function set of the reset sequence
+    pub reset_second: u64,
+    /// execution time of clear and home
+    pub clear_home: u64,
+    /// execution time of the other instructions and of the reads/writes of the RAM
+    pub execution: u64,
+    /// enable high
+    pub enable_pulse: u64,
+    /// from a rising edge of the enable to the next
+    pub enable_cycle: u64,
+}
+
+/// timings of the HD44780 datasheet (fosc = 270 kHz)
+pub const HD44780_TIMINGS: Timings = Timings {
+    power_on: 40_000_000,
+    reset_first: 4_100_000,
+    reset_second: 100_000,
+    clear_home: 1_520_000,
+    execution: 37_000,
+    enable_pulse: 450,
+    enable_cycle: 1_000,
+};
+
+/// ### time that was not respected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViolationKind {
+    PowerOn,
+    Reset,
+    /// the previous operation was still running
+    Execution(Operation),
+    EnablePulse,
+    EnableCycle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// time of the edge in ns
+    pub time: u64,
+    pub kind: ViolationKind,
+    pub required: u64,
+    pub actual: u64,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.kind {
+            ViolationKind::PowerOn => "power on delay".to_string(),
+            ViolationKind::Reset => "reset sequence delay".to_string(),
+            ViolationKind::Execution(operation) => format!("execution time of {:02X?}", operation),
+            ViolationKind::EnablePulse => "enable pulse width".to_string(),
+            ViolationKind::EnableCycle => "enable cycle time".to_string(),
+        };
+        write!(
+            f,
+            "{} ns: {} is {} ns, required {} ns",
+            self.time, what, self.actual, self.required
+        )
+    }
+}
+
+/// ### a `send` with its time in ns
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimedSend {
+    pub time: u64,
+    pub config: u8,
+    pub data: u8,
+}
+
+/// ### checks the timings of an `Interface`
+/// records each `send` with the time of the `VirtualClock` and checks the power on and reset
+/// sequence delays, the execution times and the enable pulses, then forwards it to `interface`.
+/// on `Bytes` interfaces each byte is an operation (no reset sequence and enable pulses),
+/// the bytes of a `send_bytes` frame are executed one after the other
+///
+/// ```
+/// # use liquid_crystal::prelude::*;
+/// # use liquid_crystal::emulator::*;
+/// let clock = VirtualClock::new();
+/// let mut delay = VirtualDelay::new(clock.clone());
+/// let mut checker = TimingChecker::new(HD44780::new(), clock, HD44780_TIMINGS);
+/// let mut lcd = LiquidCrystal::new(&mut checker, Bus4Bits, LCD16X2);
+/// lcd.begin(&mut delay);
+/// checker.assert_ok();
+/// ```
+pub struct TimingChecker<T: Interface> {
+    interface: T,
+    clock: VirtualClock,
+    timings: Timings,
+    decoder: BusDecoder,
+    config: u8,
+    rise: Option<u64>,
+    busy_start: u64,
+    busy: u64,
+    busy_kind: ViolationKind,
+    reset_step: u8,
+    records: Vec<TimedSend>,
+    violations: Vec<Violation>,
+}
+
+impl<T: Interface> TimingChecker<T> {
+    /// ### checker of the controller on `EN`
+    pub fn new(interface: T, clock: VirtualClock, timings: Timings) -> TimingChecker<T> {
+        TimingChecker::on_enable(interface, clock, timings, EN)
+    }
+
+    /// ### checker of the controller on `enable` (`EN` or `EN << 1`)
+    pub fn on_enable(
+        interface: T,
+        clock: VirtualClock,
+        timings: Timings,
+        enable: u8,
+    ) -> TimingChecker<T> {
+        TimingChecker {
+            interface,
+            clock,
+            timings,
+            decoder: BusDecoder::new(enable),
+            config: 0,
+            rise: None,
+            busy_start: 0,
+            busy: timings.power_on,
+            busy_kind: ViolationKind::PowerOn,
+            // the controllers with native I2C/SPI are initialized without the reset sequence
+            reset_step: if T::KIND == InterfaceKind::Bytes {
+                2
+            } else {
+                0
+            },
+            records: Vec::new(),
+            violations: Vec::new(),
+        }
+    }
+
+    /// ### the sends with their time
+    pub fn records(&self) -> &[TimedSend] {
+        &self.records
+    }
+
+    /// ### the timings not respected
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// ### panics with the list of violations
+    pub fn assert_ok(&self) {
+        if !self.violations.is_empty() {
+            let list: Vec<String> = self.violations.iter().map(|v| v.to_string()).collect();
+            panic!("timing violations:\n{}", list.join("\n"));
+        }
+    }
+
+    pub fn interface(&mut self) -> &mut T {
+        &mut self.interface
+    }
+
+    pub fn release(self) -> T {
+        self.interface
+    }
+
+    fn violation(&mut self, kind: ViolationKind, required: u64, actual: u64) {
+        self.violations.push(Violation {
+            time: self.clock.now(),
+            kind,
+            required,
+            actual,
+        });
+    }
+
+    fn rising_edge(&mut self, now: u64, config: u8) {
+        if let Some(rise) = self.rise {
+            if now - rise < self.timings.enable_cycle {
+                self.violation(
+                    ViolationKind::EnableCycle,
+                    self.timings.enable_cycle,
+                    now - rise,
+                );
+            }
+        }
+        self.rise = Some(now);
+        if !self.decoder.nibble_pending() {
+            self.check_busy(now, config);
+        }
+    }
+
+    /// an operation starts while the previous one is running
+    fn check_busy(&mut self, now: u64, config: u8) {
+        // the busy flag can be read while the controller is busy
+        let busy_flag_read = (config & _RW) != 0 && (config & RS) == 0;
+        if !busy_flag_read && now < self.busy_start + self.busy {
+            self.violation(self.busy_kind, self.busy, now - self.busy_start);
+        }
+    }
+
+    /// operations of a `Bytes` interface
+    fn byte_operations(&mut self, now: u64, config: u8, data: &[u8]) {
+        self.check_busy(now, config);
+        let mut busy = 0;
+        for byte in data {
+            self.records.push(TimedSend {
+                time: now,
+                config,
+                data: *byte,
+            });
+            let operation = Transaction::from_pair(config, *byte).operation;
+            self.start_operation(now, operation);
+            busy += self.busy;
+        }
+        self.busy = busy;
+    }
+
+    fn falling_edge(&mut self, now: u64) {
+        if let Some(rise) = self.rise {
+            if now - rise < self.timings.enable_pulse {
+                self.violation(
+                    ViolationKind::EnablePulse,
+                    self.timings.enable_pulse,
+                    now - rise,
+                );
+            }
+        }
+    }
+
+    fn start_operation(&mut self, now: u64, operation: Operation) {
+        let is_reset = matches!(operation, Operation::Command(0x30..=0x3F)) && self.reset_step < 2;
+        let (kind, busy) = if is_reset {
+            self.reset_step += 1;
+            let busy = if self.reset_step == 1 {
+                self.timings.reset_first
+            } else {
+                self.timings.reset_second
+            };
+            (ViolationKind::Reset, busy)
+        } else {
+            self.reset_step = 2;
+            let busy = match operation {
+                Operation::Command(0x01..=0x03) => self.timings.clear_home,
+                Operation::Read { rs: false } => 0,
+                _ => self.timings.execution,
+            };
+            (ViolationKind::Execution(operation), busy)
+        };
+        self.busy_start = now;
+        self.busy = busy;
+        self.busy_kind = kind;
+    }
+}
+
+impl<T: Interface> Interface for TimingChecker<T> {
+    const KIND: InterfaceKind = T::KIND;
+
+    fn send(&mut self, config: u8, data: u8) {
+        let now = self.clock.now();
+        if T::KIND == InterfaceKind::Bytes {
+            self.byte_operations(now, config, &[data]);
+            self.interface.send(config, data);
+            return;
+        }
+        self.records.push(TimedSend {
+            time: now,
+            config,
+            data,
+        });
+        let was_high = self.decoder.enable_high(self.config);
+        let high = self.decoder.enable_high(config);
+        self.config = config;
+        if high && !was_high {
+            self.rising_edge(now, config);
+        } else if was_high && !high {
+            self.falling_edge(now);
+        }
+        if let Some(operation) = self.decoder.decode(config, data) {
+            self.start_operation(now, operation);
+        }
+        self.interface.send(config, data);
+    }
+
+    fn send_bytes(&mut self, config: u8, data: &[u8]) {
+        if T::KIND == InterfaceKind::Bytes {
+            self.byte_operations(self.clock.now(), config, data);
+            self.interface.send_bytes(config, data);
+        } else {
+            for byte in data {
+                self.send(config, *byte);
+            }
+        }
+    }
+}
+
+impl<T: ReadInterface> ReadInterface for TimingChecker<T> {
+    fn read(&mut self) -> u8 {
+        self.interface.read()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::HD44780;
+    use crate::lcd_trait::{BusBits, LiquidCrystal, SendType, LCD16X2};
+
+    /// `Bytes` interface that keeps the bytes
+    #[derive(Default)]
+    struct Bytes {
+        sends: Vec<(u8, u8)>,
+        frames: usize,
+    }
+
+    impl Interface for Bytes {
+        const KIND: InterfaceKind = InterfaceKind::Bytes;
+
+        fn send(&mut self, config: u8, data: u8) {
+            self.sends.push((config, data));
+        }
+
+        fn send_bytes(&mut self, config: u8, data: &[u8]) {
+            self.frames += 1;
+            for byte in data {
+                self.send(config, *byte);
+            }
+        }
+    }
+
+    fn checker<T: Interface>(interface: T) -> (TimingChecker<T>, VirtualDelay) {
+        let clock = VirtualClock::new();
+        let delay = VirtualDelay::new(clock.clone());
+        (TimingChecker::new(interface, clock, HD44780_TIMINGS), delay)
+    }
+
+    #[test]
+    fn driver_respects_the_timings() {
+        for bus in [BusBits::Bus4Bits, BusBits::Bus8Bits] {
+            let (mut checker, mut delay) = checker(HD44780::new());
+            let mut lcd = LiquidCrystal::new(&mut checker, bus, LCD16X2);
+            lcd.begin(&mut delay);
+            lcd.write(&mut delay, SendType::Text("hello"));
+            lcd.custom_char(&mut delay, &[0x1F; 8], 1);
+            lcd.set_cursor(&mut delay, 1, 0)
+                .write(&mut delay, SendType::CustomChar(1));
+
+            checker.assert_ok();
+            assert_eq!(checker.interface().line(0), "hello");
+            assert_eq!(checker.interface().ddram()[0x40], 1);
+        }
+    }
+
+    #[test]
+    fn forwards_bytes_interfaces() {
+        let (mut checker, mut delay) = checker(Bytes::default());
+        let mut lcd = LiquidCrystal::new(&mut checker, BusBits::Bus4Bits, LCD16X2);
+        lcd.begin(&mut delay);
+        lcd.write(&mut delay, SendType::Text("hello"));
+        lcd.custom_char(&mut delay, &[0x1F; 8], 1);
+
+        checker.assert_ok();
+        let bytes = checker.release();
+        assert_eq!(bytes.sends[0], (0, 0x38));
+        assert_eq!(bytes.frames, 1);
+        let text: Vec<u8> = bytes
+            .sends
+            .iter()
+            .filter(|(config, _)| *config == RS)
+            .map(|(_, data)| *data)
+            .take(5)
+            .collect();
+        assert_eq!(text, b"hello");
+    }
+
+    #[test]
+    fn frame_takes_the_time_of_each_byte() {
+        let (mut checker, delay) = checker(Bytes::default());
+        delay.clock().advance(50_000_000);
+        checker.send(0, 0x38);
+        delay.clock().advance(40_000);
+        checker.send_bytes(RS, b"hello");
+        delay.clock().advance(40_000);
+        checker.send(0, 0x80);
+
+        assert_eq!(checker.records().len(), 7);
+        assert_eq!(checker.violations().len(), 1);
+        assert_eq!(
+            checker.violations()[0].kind,
+            ViolationKind::Execution(Operation::Data(b'o'))
+        );
+    }
+
+    #[test]
+    fn short_power_on_delay() {
+        let (mut checker, delay) = checker(HD44780::new());
+        delay.clock().advance(10_000_000);
+        checker.send(0, 0x30);
+        checker.send(EN, 0x30);
+        delay.clock().advance(1_000);
+        checker.send(0, 0x30);
+
+        let violation = checker.violations()[0];
+        assert_eq!(violation.kind, ViolationKind::PowerOn);
+        assert_eq!(violation.required, 40_000_000);
+        assert_eq!(violation.actual, 10_000_000);
+    }
+
+    #[test]
+    fn short_enable_pulse() {
+        let (mut checker, delay) = checker(HD44780::new());
+        delay.clock().advance(50_000_000);
+        checker.send(0, 0x30);
+        checker.send(EN, 0x30);
+        delay.clock().advance(100);
+        checker.send(0, 0x30);
+
+        assert_eq!(
+            checker.violations(),
+            &[Violation {
+                time: 50_000_100,
+                kind: ViolationKind::EnablePulse,
+                required: 450,
+                actual: 100,
+            }]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "timing violations")]
+    fn assert_ok_panics() {
+        let (mut checker, _) = checker(HD44780::new());
+        checker.send(EN, 0x30);
+        checker.assert_ok();
+    }
+}
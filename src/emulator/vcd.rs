@@ -0,0 +1,189 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use super::timing::VirtualClock;
+use crate::lcd_trait::{Interface, InterfaceKind, ReadInterface, _RW, EN, RS};
+
+/// names of the 1 bit signals, `BL` is the backlight
+const SIGNALS: [&str; 13] = [
+    "RS", "RW", "EN", "EN2", "D0", "D1", "D2", "D3", "D4", "D5", "D6", "D7", "BL",
+];
+/// identifier of the 8 bits data bus
+const DATA_ID: char = '.';
+
+/// ### records the pins of an interface in a Value Change Dump
+/// each `send` is timestamped with the `VirtualClock` (advanced by `VirtualDelay`),
+/// the VCD has RS, RW, EN, EN2, D0 - D7, the data bus and the backlight (`set_backlight`).
+/// the bytes of `Bytes` interfaces are shown on the data bus with RS and RW, without enable pulses
+///
+/// ```no_run
+/// # use liquid_crystal::prelude::*;
+/// # use liquid_crystal::emulator::*;
+/// # fn main() -> std::io::Result<()> {
+/// let clock = VirtualClock::new();
+/// let mut delay = VirtualDelay::new(clock.clone());
+/// let mut vcd = VcdRecorder::new(HD44780::new(), clock);
+/// let mut lcd = LiquidCrystal::new(&mut vcd, Bus4Bits, LCD16X2);
+/// lcd.begin(&mut delay);
+/// vcd.write_vcd(std::fs::File::create("begin.vcd")?)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct VcdRecorder<T: Interface> {
+    interface: T,
+    clock: VirtualClock,
+    state: u16,
+    changes: Vec<(u64, u16)>,
+}
+
+impl<T: Interface> VcdRecorder<T> {
+    pub fn new(interface: T, clock: VirtualClock) -> VcdRecorder<T> {
+        VcdRecorder {
+            interface,
+            clock,
+            state: 0,
+            changes: Vec::new(),
+        }
+    }
+
+    /// ### records a change of the backlight
+    pub fn set_backlight(&mut self, state: bool) {
+        let word = (self.state & 0x0FFF) | ((state as u16) << 12);
+        self.change(word);
+    }
+
+    pub fn interface(&mut self) -> &mut T {
+        &mut self.interface
+    }
+
+    pub fn release(self) -> T {
+        self.interface
+    }
+
+    /// ### the Value Change Dump, with a timescale of 1 ns
+    pub fn vcd(&self) -> String {
+        let mut vcd = String::new();
+        vcd.push_str("$version liquid_crystal $end\n$timescale 1ns $end\n$scope module lcd $end\n");
+        for (n, name) in SIGNALS.iter().enumerate() {
+            let _ = writeln!(vcd, "$var wire 1 {} {} $end", id(n), name);
+        }
+        let _ = writeln!(vcd, "$var wire 8 {} DATA [7:0] $end", DATA_ID);
+        vcd.push_str("$upscope $end\n$enddefinitions $end\n#0\n$dumpvars\n");
+        dump(&mut vcd, 0xFFFF, 0);
+        vcd.push_str("$end\n");
+        let mut last = 0;
+        let mut last_time = Some(0);
+        for (time, word) in &self.changes {
+            if last_time != Some(*time) {
+                let _ = writeln!(vcd, "#{}", time);
+                last_time = Some(*time);
+            }
+            dump(&mut vcd, last ^ word, *word);
+            last = *word;
+        }
+        vcd
+    }
+
+    pub fn write_vcd(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(self.vcd().as_bytes())
+    }
+
+    fn record(&mut self, config: u8, data: u8) {
+        let mut word = self.state & 0x1000;
+        for (n, flag) in [RS, _RW, EN, EN << 1].iter().enumerate() {
+            if (config & flag) != 0 {
+                word |= 1 << n;
+            }
+        }
+        word |= (data as u16) << 4;
+        self.change(word);
+    }
+
+    fn change(&mut self, word: u16) {
+        if word != self.state {
+            self.state = word;
+            self.changes.push((self.clock.now(), word));
+        }
+    }
+}
+
+impl<T: Interface> Interface for VcdRecorder<T> {
+    const KIND: InterfaceKind = T::KIND;
+
+    fn send(&mut self, config: u8, data: u8) {
+        self.record(config, data);
+        self.interface.send(config, data);
+    }
+
+    fn send_bytes(&mut self, config: u8, data: &[u8]) {
+        for byte in data {
+            self.record(config, *byte);
+        }
+        self.interface.send_bytes(config, data);
+    }
+}
+
+impl<T: ReadInterface> ReadInterface for VcdRecorder<T> {
+    fn read(&mut self) -> u8 {
+        self.interface.read()
+    }
+}
+
+/// identifier of the signal `n`
+fn id(n: usize) -> char {
+    (b'!' + n as u8) as char
+}
+
+/// writes the signals of `mask` with their state in `word`
+fn dump(vcd: &mut String, mask: u16, word: u16) {
+    for n in 0..SIGNALS.len() {
+        if mask & (1 << n) != 0 {
+            let _ = writeln!(vcd, "{}{}", (word >> n) & 1, id(n));
+        }
+    }
+    if mask & 0x0FF0 != 0 {
+        let _ = writeln!(vcd, "b{:08b} {}", (word >> 4) as u8, DATA_ID);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::{VirtualDelay, HD44780};
+    use crate::lcd_trait::{BusBits, LiquidCrystal, LCD16X2};
+
+    #[test]
+    fn records_begin() {
+        let clock = VirtualClock::new();
+        let mut delay = VirtualDelay::new(clock.clone());
+        let mut recorder = VcdRecorder::new(HD44780::new(), clock);
+        recorder.set_backlight(true);
+        let mut lcd = LiquidCrystal::new(&mut recorder, BusBits::Bus4Bits, LCD16X2);
+        lcd.begin(&mut delay);
+        let vcd = recorder.vcd();
+
+        for (n, name) in SIGNALS.iter().enumerate() {
+            let var = format!("$var wire 1 {} {} $end", (b'!' + n as u8) as char, name);
+            assert!(vcd.contains(&var), "{}", var);
+        }
+        assert!(vcd.contains("$var wire 1 - BL $end"));
+        assert!(vcd.contains("$var wire 8 . DATA [7:0] $end"));
+        assert!(vcd.contains("$enddefinitions $end\n#0\n$dumpvars\n"));
+
+        let times: Vec<u64> = vcd
+            .lines()
+            .filter_map(|line| line.strip_prefix('#'))
+            .map(|time| time.parse().unwrap())
+            .collect();
+        assert!(times.windows(2).all(|pair| pair[0] < pair[1]));
+        // first EN pulse after the 50 ms of power on
+        assert_eq!(times[1], 50_000_000);
+
+        // backlight on at 0, then D4, D5, the data bus and EN of the first function set
+        assert!(vcd.contains("$end\n1-\n#50000000\n1)\n1*\nb00110000 .\n1#\n"));
+        assert!(vcd.contains("b00100000 .\n"));
+        assert!(vcd.contains("1#\n"));
+        assert!(vcd.contains("0#\n"));
+        assert!(recorder.interface().two_lines());
+    }
+}
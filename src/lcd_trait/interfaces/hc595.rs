@@ -0,0 +1,166 @@
+use super::{bit, Interface, PinMap, NC};
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::SpiDevice;
+
+/// Adafruit I2C/SPI backpack in SPI mode (backlight on Q7)
+pub const ADAFRUIT_BACKPACK_595: PinMap = PinMap {
+    rs: 1,
+    rw: NC,
+    en: 2,
+    en2: NC,
+    data: [NC, NC, NC, NC, 6, 5, 4, 3],
+};
+
+/// one register, same bit order of the PCF8574 modules (backlight on Q3)
+pub const HC595_4BITS: PinMap = PinMap {
+    rs: 0,
+    rw: 1,
+    en: 2,
+    en2: NC,
+    data: [NC, NC, NC, NC, 4, 5, 6, 7],
+};
+
+/// two registers, RS/RW/EN/EN2 on Q0 - Q3 of the first register and D0 - D7 on the second register
+pub const HC595_8BITS: PinMap = PinMap {
+    rs: 0,
+    rw: 1,
+    en: 2,
+    en2: 3,
+    data: [8, 9, 10, 11, 12, 13, 14, 15],
+};
+
+/// ### 74HC595 shift register over SPI
+/// the outputs are numbered from 0 to 7 (Q0 - Q7) for one register,
+/// and 8 to 15 for the second register of a chain (0 - 7 is the register connected to the MCU).
+///
+/// the register is latched on the rising edge of `latch`,
+/// if the latch is connected to the chip select of the `SpiDevice` pass `lcd_dummy`
+pub struct HC595<S: SpiDevice, L: OutputPin> {
+    spi: S,
+    latch: L,
+    pins: PinMap,
+    backlight_pin: u8,
+    backlight: bool,
+}
+
+impl<S: SpiDevice, L: OutputPin> HC595<S, L> {
+    /// `backlight` is the output of the backlight, or `NC`
+    pub fn new(spi: S, latch: L, pins: PinMap, backlight: u8) -> HC595<S, L> {
+        HC595 {
+            spi,
+            latch,
+            pins,
+            backlight_pin: backlight,
+            backlight: true,
+        }
+    }
+
+    /// ### turns the backlight on or off
+    /// applied on the next send
+    pub fn set_backlight(&mut self, state: bool) {
+        self.backlight = state;
+    }
+}
+
+impl<S: SpiDevice, L: OutputPin> Interface for HC595<S, L> {
+    fn send(&mut self, config: u8, data: u8) {
+        let word = self.pins.pack(config, data) | bit(self.backlight_pin, self.backlight);
+        let [low, high] = word.to_le_bytes();
+        self.latch.set_low();
+        if (self.pins.mask() | bit(self.backlight_pin, true)) > 0xFF {
+            self.spi.write(&[high, low]);
+        } else {
+            self.spi.write(&[low]);
+        }
+        self.latch.set_high();
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::lcd_trait::{EN, RS};
+    use core::cell::RefCell;
+    use embedded_hal::digital::ErrorType as PinErrorType;
+    use embedded_hal::spi::{ErrorType, Operation};
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Latch(bool),
+        Write(Vec<u8>),
+    }
+
+    /// the SPI writes and the latch edges, in order
+    struct Log<'a>(&'a RefCell<Vec<Event>>);
+
+    impl ErrorType for Log<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice for Log<'_> {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(bytes) = operation {
+                    self.0.borrow_mut().push(Event::Write(bytes.to_vec()));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    struct Latch<'a>(&'a RefCell<Vec<Event>>);
+
+    impl PinErrorType for Latch<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for Latch<'_> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().push(Event::Latch(false));
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().push(Event::Latch(true));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn one_register() {
+        let log = RefCell::new(Vec::new());
+        let mut hc595 = HC595::new(Log(&log), Latch(&log), ADAFRUIT_BACKPACK_595, 7);
+        hc595.send(RS | EN, 0x50);
+        hc595.set_backlight(false);
+        hc595.send(RS, 0x50);
+
+        assert_eq!(
+            log.into_inner(),
+            [
+                Event::Latch(false),
+                Event::Write(vec![0xD6]), // D4, D6, RS, EN and the backlight
+                Event::Latch(true),
+                Event::Latch(false),
+                Event::Write(vec![0x52]),
+                Event::Latch(true),
+            ]
+        );
+    }
+
+    #[test]
+    fn two_registers() {
+        let log = RefCell::new(Vec::new());
+        let mut hc595 = HC595::new(Log(&log), Latch(&log), HC595_8BITS, NC);
+        hc595.send(RS | EN, 0x41);
+
+        // the second register first, it is shifted through the first one
+        assert_eq!(
+            log.into_inner(),
+            [
+                Event::Latch(false),
+                Event::Write(vec![0x41, 0x05]),
+                Event::Latch(true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use super::{bit, Interface, PinMap, NC};
+use embedded_hal::i2c::I2c;
+
+const IODIRA: u8 = 0x00;
+const GPPUA: u8 = 0x0C;
+const GPIOA: u8 = 0x12;
+const OLATA: u8 = 0x14;
+const OLATB: u8 = 0x15;
+
+/// buttons of the Adafruit RGB LCD shield, as returned by `read_buttons`
+pub const BUTTON_SELECT: u16 = 0b00001;
+pub const BUTTON_RIGHT: u16 = 0b00010;
+pub const BUTTON_DOWN: u16 = 0b00100;
+pub const BUTTON_UP: u16 = 0b01000;
+pub const BUTTON_LEFT: u16 = 0b10000;
+
+/// ### wiring of the MCP23017
+/// pins are numbered from 0 to 15 (GPA0 - GPA7 = 0 - 7, GPB0 - GPB7 = 8 - 15).
+///
+/// `red`, `green` and `blue` are the backlight LEDs (`NC` if not present),
+/// `buttons` is the mask of the pins used as buttons (inputs with pull-up, active low)
+#[derive(Clone, Copy)]
+pub struct MCP23017Config {
+    pub pins: PinMap,
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub backlight_active_low: bool,
+    pub buttons: u16,
+}
+
+/// Adafruit RGB LCD shield (and clones), 4 bits bus
+pub const ADAFRUIT_RGB_SHIELD: MCP23017Config = MCP23017Config {
+    pins: PinMap {
+        rs: 15,
+        rw: 14,
+        en: 13,
+        en2: NC,
+        data: [NC, NC, NC, NC, 12, 11, 10, 9],
+    },
+    red: 6,
+    green: 7,
+    blue: 8,
+    backlight_active_low: true,
+    buttons: 0b0001_1111,
+};
+
+/// 8 bits bus, D0 - D7 on port B, EN/RW/RS on GPA5 - GPA7 and buttons on GPA0 - GPA4
+pub const MCP23017_8BITS: MCP23017Config = MCP23017Config {
+    pins: PinMap {
+        rs: 7,
+        rw: 6,
+        en: 5,
+        en2: NC,
+        data: [8, 9, 10, 11, 12, 13, 14, 15],
+    },
+    red: NC,
+    green: NC,
+    blue: NC,
+    backlight_active_low: false,
+    buttons: 0b0001_1111,
+};
+
+pub struct MCP23017<T: I2c> {
+    i2c_bus: T,
+    addr: u8,
+    config: MCP23017Config,
+    port: u16,
+}
+
+impl<T: I2c> MCP23017<T> {
+    /// ### creates the interface and configures the MCP23017 pins
+    /// the backlight starts on (white)
+    pub fn new(i2c_bus: T, addr: u8, config: MCP23017Config) -> MCP23017<T> {
+        let mut mcp = MCP23017 {
+            i2c_bus,
+            addr,
+            config,
+            port: 0,
+        };
+        mcp.port = mcp.backlight_bits(true, true, true);
+        let [dir_a, dir_b] = (!(config.pins.mask() | mcp.backlight_mask())).to_le_bytes();
+        let [pullup_a, pullup_b] = config.buttons.to_le_bytes();
+        let [port_a, port_b] = mcp.port.to_le_bytes();
+        mcp.i2c_bus.write(addr, &[IODIRA, dir_a, dir_b]);
+        mcp.i2c_bus.write(addr, &[GPPUA, pullup_a, pullup_b]);
+        mcp.i2c_bus.write(addr, &[OLATA, port_a, port_b]);
+        mcp
+    }
+
+    /// ### turns each backlight LED on or off
+    /// the port is kept as it was if the write fails
+    pub fn set_backlight(&mut self, red: bool, green: bool, blue: bool) -> Result<(), T::Error> {
+        let port = (self.port & !self.backlight_mask()) | self.backlight_bits(red, green, blue);
+        self.write_port(port)
+    }
+
+    /// ### reads the buttons
+    /// returns the mask of the pressed buttons, only the inputs are read,
+    /// so the display pins are not changed
+    pub fn read_buttons(&mut self) -> Result<u16, T::Error> {
+        let mut gpio = [0; 2];
+        self.i2c_bus.write_read(self.addr, &[GPIOA], &mut gpio)?;
+        let levels = u16::from_le_bytes(gpio);
+        Ok(!levels & self.config.buttons)
+    }
+
+    fn backlight_mask(&self) -> u16 {
+        bit(self.config.red, true) | bit(self.config.green, true) | bit(self.config.blue, true)
+    }
+
+    fn backlight_bits(&self, red: bool, green: bool, blue: bool) -> u16 {
+        let on = !self.config.backlight_active_low;
+        bit(self.config.red, red == on)
+            | bit(self.config.green, green == on)
+            | bit(self.config.blue, blue == on)
+    }
+
+    /// only writes the ports that changed, the port is updated after a successful write
+    fn write_port(&mut self, port: u16) -> Result<(), T::Error> {
+        let changed = port ^ self.port;
+        let [a, b] = port.to_le_bytes();
+        if changed == 0 {
+            return Ok(());
+        } else if changed & 0xFF00 == 0 {
+            self.i2c_bus.write(self.addr, &[OLATA, a])?;
+        } else if changed & 0x00FF == 0 {
+            self.i2c_bus.write(self.addr, &[OLATB, b])?;
+        } else {
+            self.i2c_bus.write(self.addr, &[OLATA, a, b])?;
+        }
+        self.port = port;
+        Ok(())
+    }
+}
+
+impl<T: I2c> Interface for MCP23017<T> {
+    fn send(&mut self, config: u8, data: u8) {
+        let pins = self.config.pins;
+        let port = (self.port & !pins.mask()) | pins.pack(config, data);
+        self.write_port(port);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal::i2c::{ErrorKind, ErrorType, Operation};
+
+    /// records the writes, answers the reads with `gpio`, fails while `fail` is set
+    struct Bus {
+        writes: [[u8; 3]; 8],
+        count: usize,
+        gpio: [u8; 2],
+        fail: bool,
+    }
+
+    impl Bus {
+        fn new() -> Bus {
+            Bus {
+                writes: [[0; 3]; 8],
+                count: 0,
+                gpio: [0xFF; 2],
+                fail: false,
+            }
+        }
+
+        fn writes(&self) -> &[[u8; 3]] {
+            &self.writes[..self.count]
+        }
+    }
+
+    impl ErrorType for Bus {
+        type Error = ErrorKind;
+    }
+
+    impl I2c for Bus {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            assert_eq!(address, 0x20);
+            if self.fail {
+                return Err(ErrorKind::Bus);
+            }
+            for operation in operations {
+                match operation {
+                    Operation::Write(bytes) => {
+                        self.writes[self.count][..bytes.len()].copy_from_slice(bytes);
+                        self.count += 1;
+                    }
+                    Operation::Read(buffer) => buffer.copy_from_slice(&self.gpio),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn configures_the_pins() {
+        let mut bus = Bus::new();
+        MCP23017::new(&mut bus, 0x20, ADAFRUIT_RGB_SHIELD);
+        // outputs: display on GPA6, GPA7, GPB0 - GPB7 and the LEDs, active low (off = high)
+        assert_eq!(
+            bus.writes(),
+            [
+                [IODIRA, 0x3F, 0x00],
+                [GPPUA, 0x1F, 0x00],
+                [OLATA, 0x00, 0x00]
+            ]
+        );
+    }
+
+    #[test]
+    fn backlight() {
+        let mut bus = Bus::new();
+        let mut mcp = MCP23017::new(&mut bus, 0x20, ADAFRUIT_RGB_SHIELD);
+        mcp.set_backlight(false, true, true).unwrap();
+        mcp.set_backlight(false, true, true).unwrap();
+        mcp.set_backlight(false, true, false).unwrap();
+        assert_eq!(
+            &bus.writes()[3..],
+            [[OLATA, 0x40, 0x00], [OLATB, 0x01, 0x00]]
+        );
+    }
+
+    #[test]
+    fn failed_backlight_keeps_the_port() {
+        let mut bus = Bus::new();
+        let mut mcp = MCP23017::new(&mut bus, 0x20, ADAFRUIT_RGB_SHIELD);
+        mcp.i2c_bus.fail = true;
+        assert_eq!(mcp.set_backlight(false, false, false), Err(ErrorKind::Bus));
+        assert_eq!(mcp.port, 0x0000);
+        mcp.i2c_bus.fail = false;
+        mcp.set_backlight(false, false, false).unwrap();
+        assert_eq!(&bus.writes()[3..], [[OLATA, 0xC0, 0x01]]);
+    }
+
+    #[test]
+    fn buttons() {
+        let mut bus = Bus::new();
+        // SELECT and LEFT pressed, the display and LED pins are ignored
+        bus.gpio = [0b0110_1110, 0x00];
+        let mut mcp = MCP23017::new(&mut bus, 0x20, ADAFRUIT_RGB_SHIELD);
+        assert_eq!(mcp.read_buttons(), Ok(BUTTON_SELECT | BUTTON_LEFT));
+        assert_eq!(bus.writes()[3], [GPIOA, 0x00, 0x00]);
+    }
+}
@@ -1,18 +1,69 @@
 #![allow(unused)]
 
 pub mod dummy;
+pub mod hc595;
+pub mod mcp23017;
+pub mod native_i2c;
+pub mod parallel_8bits;
+pub mod parallel_rw;
+pub mod pca963x;
+pub mod pin_map;
+pub mod port;
+pub mod serial_spi;
+#[cfg(feature = "serial")]
+pub mod serlcd;
+pub mod shift_register;
+pub use hc595::*;
+pub use mcp23017::*;
+pub use native_i2c::*;
+pub use parallel_8bits::*;
+pub use parallel_rw::*;
+pub use pca963x::*;
+pub use pin_map::*;
+pub use port::*;
+pub use serial_spi::*;
+#[cfg(feature = "serial")]
+pub use serlcd::*;
+pub use shift_register::*;
 use embedded_hal::i2c::I2c;
 use embedded_hal::digital::OutputPin;
 
 pub const EN: u8 = 0b00000100;
-pub const _RW: u8 = 0b00000010; // NO READ FUNCTION
+pub const _RW: u8 = 0b00000010; // only used by ReadInterface
 pub const RS: u8 = 0b00000001;
 
 #[deprecated(since="0.2.0", note="This address is only valid in the LCM1602 IIC module, just type 0x27 instead")]
 pub const I2C_ADDRESS: u8 = 0x27;
 
+/// ### how `LiquidCrystal` drives the interface
+#[derive(PartialEq, Eq)]
+pub enum InterfaceKind {
+    /// `send` sets the pins, the driver generates the EN pulses and nibbles
+    Pins,
+    /// `send` transfers a whole byte (`config` only carries RS),
+    /// used by controllers with native I2C/SPI
+    Bytes,
+}
+
 pub trait Interface {
+    const KIND: InterfaceKind = InterfaceKind::Pins;
+
     fn send(&mut self, config: u8, data: u8);
+
+    /// ### sends several bytes with the same config
+    /// used by `Bytes` interfaces to send text in a single frame
+    fn send_bytes(&mut self, config: u8, data: &[u8]) {
+        for byte in data {
+            self.send(config, *byte);
+        }
+    }
+}
+
+/// ### interfaces that can read the display
+/// while the last `send` had the `_RW` bit set the data lines are released,
+/// `read` returns their levels in the same bits of `data` (D4 - D7 for 4 bits wiring)
+pub trait ReadInterface: Interface {
+    fn read(&mut self) -> u8;
 }
 
 pub struct Parallel<D1, D2, D3, D4, RS, EN, EN2>
@@ -120,6 +171,18 @@ where
     }
 }
 
+/// bit order of the PCF8574 backpacks used by `I2C`
+pub const PCF8574_BACKPACK: PinMap = PinMap {
+    rs: 0,
+    rw: 1,
+    en: 2,
+    en2: NC,
+    data: [NC, NC, NC, NC, 4, 5, 6, 7],
+};
+
+/// backlight bit of the PCF8574 backpacks (P3)
+pub const PCF8574_BACKLIGHT: u8 = 0x08;
+
 pub struct I2C<T: I2c> {
     i2c_bus: T,
     addr: u8,
@@ -133,7 +196,8 @@ impl<T: I2c> I2C<T> {
 
 impl<T: I2c> Interface for I2C<T> {
     fn send(&mut self, config: u8, data: u8) {
-        let byte = (config & 0b00000111) | (data & 0xF0) | 0x08; //ignores possible additional Enables, i2C Module does not support multiple displays
-        self.i2c_bus.write(self.addr, &[byte]); //0x08 (0b0000_1000) corresponds to the display backlight in the I2C module
+        //ignores possible additional Enables, i2C Module does not support multiple displays
+        let byte = PCF8574_BACKPACK.pack(config & (RS | _RW | EN), data) as u8 | PCF8574_BACKLIGHT;
+        self.i2c_bus.write(self.addr, &[byte]);
     }
 }
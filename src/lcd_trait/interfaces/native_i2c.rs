@@ -0,0 +1,63 @@
+use super::{Interface, InterfaceKind, PCA963x, RS};
+use embedded_hal::i2c::{I2c, Operation};
+
+/// control byte of a command (Co = 0, RS = 0)
+pub const CONTROL_COMMAND: u8 = 0x00;
+/// control byte of a data stream (Co = 0, RS = 1)
+pub const CONTROL_DATA: u8 = 0x40;
+
+/// ### controllers with native I2C (ST7032, AIP31068...)
+/// each command is prefixed by a control byte (Co/RS) instead of the PCF8574 nibbles,
+/// text is sent in a single data frame. use it with `Bus8Bits`
+///
+/// the bytes of a frame follow each other without gap, the controller needs ~40 µs
+/// to write each one: keep the bus at 100 kHz (90 µs per byte), not 400 kHz
+pub struct NativeI2C<T: I2c> {
+    i2c_bus: T,
+    addr: u8,
+}
+
+impl<T: I2c> NativeI2C<T> {
+    /// usually 0x3E
+    pub fn new(i2c_bus: T, addr: u8) -> NativeI2C<T> {
+        NativeI2C { i2c_bus, addr }
+    }
+
+    /// ### RGB backlight controller on the same bus
+    /// ```ignore
+    /// lcd.interface().pca963x(0x62, GROVE_RGB_CHANNELS).set_color(0, 128, 255);
+    /// ```
+    pub fn pca963x(&mut self, addr: u8, channels: [u8; 3]) -> PCA963x<&mut T> {
+        PCA963x::new(&mut self.i2c_bus, addr, channels)
+    }
+
+    fn control(config: u8) -> u8 {
+        if (config & RS) != 0 {
+            CONTROL_DATA
+        } else {
+            CONTROL_COMMAND
+        }
+    }
+}
+
+impl<T: I2c> Interface for NativeI2C<T> {
+    const KIND: InterfaceKind = InterfaceKind::Bytes;
+
+    fn send(&mut self, config: u8, data: u8) {
+        self.i2c_bus.write(self.addr, &[Self::control(config), data]);
+    }
+
+    /// the data frame relies on the bus clock (at most 100 kHz) to pace the writes
+    fn send_bytes(&mut self, config: u8, data: &[u8]) {
+        if (config & RS) != 0 {
+            self.i2c_bus.transaction(
+                self.addr,
+                &mut [Operation::Write(&[CONTROL_DATA]), Operation::Write(data)],
+            );
+        } else {
+            for command in data {
+                self.send(config, *command);
+            }
+        }
+    }
+}
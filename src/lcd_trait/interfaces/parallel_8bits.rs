@@ -0,0 +1,124 @@
+use super::{Interface, _RW, EN, RS};
+use embedded_hal::digital::OutputPin;
+
+#[inline]
+pub(crate) fn set_pin<P: OutputPin>(pin: &mut P, state: bool) {
+    if state {
+        pin.set_high();
+    } else {
+        pin.set_low();
+    }
+}
+
+/// ### parallel interface with 8 data pins
+/// works with `Bus8Bits` (and `Bus4Bits` using D4 - D7).
+/// `en2` and `rw` are optional, pass `lcd_dummy` if they are not connected
+/// (RW is kept low, connect it to GND if not used)
+pub struct Parallel8Bits<D0, D1, D2, D3, D4, D5, D6, D7, RS, RW, EN, EN2>
+where
+    D0: OutputPin,
+    D1: OutputPin,
+    D2: OutputPin,
+    D3: OutputPin,
+    D4: OutputPin,
+    D5: OutputPin,
+    D6: OutputPin,
+    D7: OutputPin,
+    RS: OutputPin,
+    RW: OutputPin,
+    EN: OutputPin,
+    EN2: OutputPin,
+{
+    d0: D0,
+    d1: D1,
+    d2: D2,
+    d3: D3,
+    d4: D4,
+    d5: D5,
+    d6: D6,
+    d7: D7,
+    rs: RS,
+    rw: RW,
+    en: EN,
+    en2: EN2,
+}
+
+impl<D0, D1, D2, D3, D4, D5, D6, D7, RS, RW, EN, EN2>
+    Parallel8Bits<D0, D1, D2, D3, D4, D5, D6, D7, RS, RW, EN, EN2>
+where
+    D0: OutputPin,
+    D1: OutputPin,
+    D2: OutputPin,
+    D3: OutputPin,
+    D4: OutputPin,
+    D5: OutputPin,
+    D6: OutputPin,
+    D7: OutputPin,
+    RS: OutputPin,
+    RW: OutputPin,
+    EN: OutputPin,
+    EN2: OutputPin,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        d0: D0,
+        d1: D1,
+        d2: D2,
+        d3: D3,
+        d4: D4,
+        d5: D5,
+        d6: D6,
+        d7: D7,
+        rs: RS,
+        rw: RW,
+        en: EN,
+        en2: EN2,
+    ) -> Parallel8Bits<D0, D1, D2, D3, D4, D5, D6, D7, RS, RW, EN, EN2> {
+        Parallel8Bits {
+            d0,
+            d1,
+            d2,
+            d3,
+            d4,
+            d5,
+            d6,
+            d7,
+            rs,
+            rw,
+            en,
+            en2,
+        }
+    }
+}
+
+impl<D0, D1, D2, D3, D4, D5, D6, D7, RS, RW, EN, EN2> Interface
+    for Parallel8Bits<D0, D1, D2, D3, D4, D5, D6, D7, RS, RW, EN, EN2>
+where
+    D0: OutputPin,
+    D1: OutputPin,
+    D2: OutputPin,
+    D3: OutputPin,
+    D4: OutputPin,
+    D5: OutputPin,
+    D6: OutputPin,
+    D7: OutputPin,
+    RS: OutputPin,
+    RW: OutputPin,
+    EN: OutputPin,
+    EN2: OutputPin,
+{
+    fn send(&mut self, config: u8, data: u8) {
+        set_pin(&mut self.d0, data & 0b0000_0001 != 0);
+        set_pin(&mut self.d1, data & 0b0000_0010 != 0);
+        set_pin(&mut self.d2, data & 0b0000_0100 != 0);
+        set_pin(&mut self.d3, data & 0b0000_1000 != 0);
+        set_pin(&mut self.d4, data & 0b0001_0000 != 0);
+        set_pin(&mut self.d5, data & 0b0010_0000 != 0);
+        set_pin(&mut self.d6, data & 0b0100_0000 != 0);
+        set_pin(&mut self.d7, data & 0b1000_0000 != 0);
+        set_pin(&mut self.rs, config & RS != 0);
+        set_pin(&mut self.rw, config & _RW != 0);
+        set_pin(&mut self.en, config & EN != 0);
+        set_pin(&mut self.en2, config & (EN << 1) != 0);
+    }
+}
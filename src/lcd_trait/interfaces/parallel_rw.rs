@@ -0,0 +1,146 @@
+use super::parallel_8bits::set_pin;
+use super::{Interface, ReadInterface, _RW, EN, RS};
+use embedded_hal::digital::{InputPin, OutputPin};
+
+/// ### a pin that can be switched between output and input
+/// implement it for the flexible pins of your HAL, or use `PinPair`
+pub trait FlexPin {
+    /// drives the pin as output
+    fn set_output(&mut self, state: bool);
+    /// releases the pin as input
+    fn set_input(&mut self);
+    /// reads the level of the pin (as input)
+    fn read(&mut self) -> bool;
+}
+
+/// ### output and input pins connected to the same data line
+/// the output must be open drain (with pull-up), it is released (high) while reading
+pub struct PinPair<O: OutputPin, I: InputPin> {
+    output: O,
+    input: I,
+}
+
+impl<O: OutputPin, I: InputPin> PinPair<O, I> {
+    pub fn new(output: O, input: I) -> PinPair<O, I> {
+        PinPair { output, input }
+    }
+}
+
+impl<O: OutputPin, I: InputPin> FlexPin for PinPair<O, I> {
+    fn set_output(&mut self, state: bool) {
+        set_pin(&mut self.output, state);
+    }
+
+    fn set_input(&mut self) {
+        self.output.set_high();
+    }
+
+    fn read(&mut self) -> bool {
+        self.input.is_high().unwrap_or(false)
+    }
+}
+
+/// ### parallel interface with RW pin and bidirectional data lines
+/// `data` holds D4 - D7 (4 pins) or D0 - D7 (8 pins),
+/// the data pins are released as inputs while RW is high.
+/// `en2` is optional, pass `lcd_dummy` if it is not connected
+pub struct ParallelRW<P, const N: usize, RS, RW, EN, EN2>
+where
+    P: FlexPin,
+    RS: OutputPin,
+    RW: OutputPin,
+    EN: OutputPin,
+    EN2: OutputPin,
+{
+    data: [P; N],
+    rs: RS,
+    rw: RW,
+    en: EN,
+    en2: EN2,
+    reading: bool,
+}
+
+impl<P, const N: usize, RS, RW, EN, EN2> ParallelRW<P, N, RS, RW, EN, EN2>
+where
+    P: FlexPin,
+    RS: OutputPin,
+    RW: OutputPin,
+    EN: OutputPin,
+    EN2: OutputPin,
+{
+    /// 4 or 8 data pins, checked at compile time
+    const DATA_PINS: () = assert!(N == 4 || N == 8, "ParallelRW takes 4 or 8 data pins");
+
+    pub fn new(
+        data: [P; N],
+        rs: RS,
+        rw: RW,
+        en: EN,
+        en2: EN2,
+    ) -> ParallelRW<P, N, RS, RW, EN, EN2> {
+        let () = Self::DATA_PINS;
+        ParallelRW {
+            data,
+            rs,
+            rw,
+            en,
+            en2,
+            reading: false,
+        }
+    }
+
+    /// position of the first data pin in the data bits
+    fn first_bit() -> usize {
+        8 - N
+    }
+}
+
+impl<P, const N: usize, RS, RW, EN, EN2> Interface for ParallelRW<P, N, RS, RW, EN, EN2>
+where
+    P: FlexPin,
+    RS: OutputPin,
+    RW: OutputPin,
+    EN: OutputPin,
+    EN2: OutputPin,
+{
+    fn send(&mut self, config: u8, data: u8) {
+        if (config & _RW) != 0 {
+            // release the data lines before the display drives them
+            if !self.reading {
+                for pin in self.data.iter_mut() {
+                    pin.set_input();
+                }
+                self.reading = true;
+            }
+            self.rw.set_high();
+        } else {
+            self.rw.set_low();
+            for (n, pin) in self.data.iter_mut().enumerate() {
+                pin.set_output(data & (1 << (n + Self::first_bit())) != 0);
+            }
+            self.reading = false;
+        }
+        set_pin(&mut self.rs, (config & RS) != 0);
+        set_pin(&mut self.en, (config & EN) != 0);
+        set_pin(&mut self.en2, (config & (EN << 1)) != 0);
+    }
+}
+
+impl<P, const N: usize, RS, RW, EN, EN2> ReadInterface for ParallelRW<P, N, RS, RW, EN, EN2>
+where
+    P: FlexPin,
+    RS: OutputPin,
+    RW: OutputPin,
+    EN: OutputPin,
+    EN2: OutputPin,
+{
+    fn read(&mut self) -> u8 {
+        let mut data = 0;
+        for (n, pin) in self.data.iter_mut().enumerate() {
+            if pin.read() {
+                data |= 1 << (n + Self::first_bit());
+            }
+        }
+        data
+    }
+}
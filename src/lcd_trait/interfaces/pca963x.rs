@@ -0,0 +1,119 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::I2c;
+
+const MODE1: u8 = 0x00;
+const MODE2: u8 = 0x01;
+const PWM0: u8 = 0x02;
+const GRPPWM: u8 = 0x06;
+const GRPFREQ: u8 = 0x07;
+const LEDOUT: u8 = 0x08;
+const AUTO_INCREMENT: u8 = 0x80;
+
+const MODE2_DMBLNK: u8 = 0x20;
+/// all LEDs controlled by their PWM and by the group PWM/blink
+const LEDOUT_GROUP: u8 = 0xFF;
+
+/// PWM channels of the red, green and blue LEDs of the Grove RGB LCD
+pub const GROVE_RGB_CHANNELS: [u8; 3] = [2, 1, 0];
+
+/// ### PCA9633/PCA9632 RGB backlight controller (Grove RGB LCD)
+/// the Grove RGB LCD uses the address 0x62.
+///
+/// it can share the bus with the display (`embedded-hal-bus`),
+/// or be borrowed from a `NativeI2C` interface with `NativeI2C::pca963x`
+pub struct PCA963x<T: I2c> {
+    i2c_bus: T,
+    addr: u8,
+    channels: [u8; 3],
+}
+
+impl<T: I2c> PCA963x<T> {
+    /// `channels` are the PWM channels of the red, green and blue LEDs
+    pub fn new(i2c_bus: T, addr: u8, channels: [u8; 3]) -> PCA963x<T> {
+        PCA963x {
+            i2c_bus,
+            addr,
+            channels,
+        }
+    }
+
+    /// ### wakes up the controller and enables the PWM of all LEDs
+    pub fn begin(&mut self, delay: &mut impl DelayNs) -> Result<(), T::Error> {
+        self.i2c_bus.write(self.addr, &[MODE1, 0x00])?;
+        delay.delay_us(500);
+        self.i2c_bus.write(self.addr, &[MODE2, 0x00])?;
+        self.i2c_bus.write(self.addr, &[LEDOUT, LEDOUT_GROUP])
+    }
+
+    /// ### sets the PWM of a channel (0 - 3)
+    pub fn set_pwm(&mut self, channel: u8, value: u8) -> Result<(), T::Error> {
+        self.i2c_bus
+            .write(self.addr, &[PWM0 + (channel & 0x03), value])
+    }
+
+    /// ### sets the backlight color
+    pub fn set_color(&mut self, red: u8, green: u8, blue: u8) -> Result<(), T::Error> {
+        let mut pwm = self.read_pwm()?;
+        for (channel, value) in self.channels.iter().zip([red, green, blue]) {
+            pwm[(*channel & 0x03) as usize] = value;
+        }
+        self.i2c_bus.write(
+            self.addr,
+            &[PWM0 | AUTO_INCREMENT, pwm[0], pwm[1], pwm[2], pwm[3]],
+        )
+    }
+
+    /// ### returns the current color (red, green, blue)
+    pub fn color(&mut self) -> Result<[u8; 3], T::Error> {
+        let pwm = self.read_pwm()?;
+        Ok(self.channels.map(|channel| pwm[(channel & 0x03) as usize]))
+    }
+
+    /// ### blinks all LEDs
+    /// `period_ms` goes from 42 ms to 10.73 s, `on` is the duty cycle (0 - 255)
+    pub fn set_group_blink(&mut self, period_ms: u32, on: u8) -> Result<(), T::Error> {
+        // GRPFREQ = period (s) x 24 - 1, limited to the register range
+        let frequency = (period_ms.saturating_mul(24) / 1000).clamp(1, 256) - 1;
+        self.i2c_bus.write(self.addr, &[MODE2, MODE2_DMBLNK])?;
+        self.i2c_bus
+            .write(self.addr, &[GRPPWM | AUTO_INCREMENT, on, frequency as u8])
+    }
+
+    /// ### dims all LEDs together (stops blinking)
+    /// 255 keeps the brightness of each channel
+    pub fn set_group_dimming(&mut self, value: u8) -> Result<(), T::Error> {
+        self.i2c_bus.write(self.addr, &[MODE2, 0x00])?;
+        self.i2c_bus.write(self.addr, &[GRPPWM, value])
+    }
+
+    /// ### fades from the current color to the new color
+    /// the color changes every 10 ms during `duration_ms`
+    pub fn fade_to(
+        &mut self,
+        delay: &mut impl DelayNs,
+        red: u8,
+        green: u8,
+        blue: u8,
+        duration_ms: u32,
+    ) -> Result<(), T::Error> {
+        let start = self.color()?;
+        let end = [red, green, blue];
+        let steps = (duration_ms / 10).max(1) as i32;
+        for step in 1..=steps {
+            let [r, g, b] = [0, 1, 2].map(|n| {
+                let diff = end[n] as i32 - start[n] as i32;
+                (start[n] as i32 + diff * step / steps) as u8
+            });
+            self.set_color(r, g, b)?;
+            delay.delay_ms(10);
+        }
+        Ok(())
+    }
+
+    fn read_pwm(&mut self) -> Result<[u8; 4], T::Error> {
+        let mut pwm = [0; 4];
+        self.i2c_bus
+            .write_read(self.addr, &[PWM0 | AUTO_INCREMENT], &mut pwm)?;
+        Ok(pwm)
+    }
+}
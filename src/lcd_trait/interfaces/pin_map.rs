@@ -0,0 +1,74 @@
+use super::{EN, RS, _RW};
+
+/// marks a display signal that is not connected to the expander
+pub const NC: u8 = 0xFF;
+
+/// ### position of each display signal on the outputs of an IO expander or shift register
+/// each field holds the output number (0 - 15) of the signal, or `NC` if it is not connected.
+///
+/// `data` goes from D0 to D7, for 4 bits wiring only D4 - D7 are used
+#[derive(Clone, Copy)]
+pub struct PinMap {
+    pub rs: u8,
+    pub rw: u8,
+    pub en: u8,
+    pub en2: u8,
+    pub data: [u8; 8],
+}
+
+impl PinMap {
+    /// ### converts the `config` and `data` of `Interface::send` into the outputs word
+    pub fn pack(&self, config: u8, data: u8) -> u16 {
+        let mut word = 0;
+        word |= bit(self.rs, config & RS != 0);
+        word |= bit(self.rw, config & _RW != 0);
+        word |= bit(self.en, config & EN != 0);
+        word |= bit(self.en2, config & (EN << 1) != 0);
+        for (n, pin) in self.data.iter().enumerate() {
+            word |= bit(*pin, data & (1 << n) != 0);
+        }
+        word
+    }
+
+    /// ### converts the outputs word back into the data bits (D0 - D7)
+    pub fn unpack_data(&self, word: u16) -> u8 {
+        let mut data = 0;
+        for (n, pin) in self.data.iter().enumerate() {
+            if bit(*pin, true) & word != 0 {
+                data |= 1 << n;
+            }
+        }
+        data
+    }
+
+    /// ### converts the outputs word back into the `config` bits (RS, RW, EN, EN2)
+    pub fn unpack_config(&self, word: u16) -> u8 {
+        let mut config = 0;
+        for (pin, flag) in [
+            (self.rs, RS),
+            (self.rw, _RW),
+            (self.en, EN),
+            (self.en2, EN << 1),
+        ] {
+            if bit(pin, true) & word != 0 {
+                config |= flag;
+            }
+        }
+        config
+    }
+
+    /// ### mask of all outputs used by the display
+    pub fn mask(&self) -> u16 {
+        self.pack(0xFF, 0xFF)
+    }
+}
+
+/// returns the word with the bit of `pin` set to `state`
+#[inline]
+pub(crate) fn bit(pin: u8, state: bool) -> u16 {
+    if state && pin < 16 {
+        1 << pin
+    } else {
+        0
+    }
+}
@@ -0,0 +1,57 @@
+use super::parallel_8bits::set_pin;
+use super::{Interface, PinMap};
+use embedded_hal::digital::OutputPin;
+
+/// ### a GPIO port that can write several pins at once
+/// implement it for the output register of your MCU (ODR, BSRR...),
+/// `write_port` must set the pins in `mask` to their value in `bits` and keep the others
+pub trait OutputPort {
+    fn write_port(&mut self, mask: u16, bits: u16);
+}
+
+/// ### adapter from individual pins to `OutputPort`
+/// bit N of the port is `pins[N]`
+pub struct PinPort<P: OutputPin, const N: usize> {
+    pins: [P; N],
+}
+
+impl<P: OutputPin, const N: usize> PinPort<P, N> {
+    /// at most 16 pins (the width of the port), checked at compile time
+    const PINS: () = assert!(N <= 16, "PinPort takes at most 16 pins");
+
+    pub fn new(pins: [P; N]) -> PinPort<P, N> {
+        let () = Self::PINS;
+        PinPort { pins }
+    }
+}
+
+impl<P: OutputPin, const N: usize> OutputPort for PinPort<P, N> {
+    fn write_port(&mut self, mask: u16, bits: u16) {
+        for (n, pin) in self.pins.iter_mut().enumerate() {
+            if mask & (1 << n) != 0 {
+                set_pin(pin, bits & (1 << n) != 0);
+            }
+        }
+    }
+}
+
+/// ### parallel interface over a whole port
+/// data, RS and EN are written in one store,
+/// `pins` holds the bit of the port of each signal
+pub struct ParallelPort<P: OutputPort> {
+    port: P,
+    pins: PinMap,
+}
+
+impl<P: OutputPort> ParallelPort<P> {
+    pub fn new(port: P, pins: PinMap) -> ParallelPort<P> {
+        ParallelPort { port, pins }
+    }
+}
+
+impl<P: OutputPort> Interface for ParallelPort<P> {
+    fn send(&mut self, config: u8, data: u8) {
+        self.port
+            .write_port(self.pins.mask(), self.pins.pack(config, data));
+    }
+}
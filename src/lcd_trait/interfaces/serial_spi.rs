@@ -0,0 +1,104 @@
+use super::{Interface, InterfaceKind, _RW, RS};
+use embedded_hal::spi::SpiDevice;
+
+/// ### synchronous serial mode of the KS0073, SSD1803A and US2066
+/// each transfer is a start byte (five 1s, RW, RS, 0) followed by the bytes
+/// sent LSB first as two nibbles padded with zeros.
+///
+/// the `SpiDevice` must be MSB first (the bits are reversed here), usually in mode 3
+///
+/// `send_bytes` packs up to 16 bytes per transfer without gap, the controller needs
+/// ~40 µs to write each one (16 clocks): keep the clock at most 400 kHz
+pub struct SerialSPI<S: SpiDevice> {
+    spi: S,
+}
+
+impl<S: SpiDevice> SerialSPI<S> {
+    pub fn new(spi: S) -> SerialSPI<S> {
+        SerialSPI { spi }
+    }
+
+    fn start_byte(config: u8) -> u8 {
+        0xF8 | ((config & _RW) << 1) | ((config & RS) << 1)
+    }
+
+    /// the low nibble and the high nibble, LSB first
+    fn encode(data: u8) -> [u8; 2] {
+        [(data & 0x0F).reverse_bits(), (data >> 4).reverse_bits()]
+    }
+}
+
+impl<S: SpiDevice> Interface for SerialSPI<S> {
+    const KIND: InterfaceKind = InterfaceKind::Bytes;
+
+    fn send(&mut self, config: u8, data: u8) {
+        let [low, high] = Self::encode(data);
+        self.spi.write(&[Self::start_byte(config), low, high]);
+    }
+
+    /// the transfer relies on the clock (at most 400 kHz) to pace the writes
+    fn send_bytes(&mut self, config: u8, data: &[u8]) {
+        let mut frame = [0; 33];
+        frame[0] = Self::start_byte(config);
+        for chunk in data.chunks(16) {
+            for (n, byte) in chunk.iter().enumerate() {
+                let [low, high] = Self::encode(*byte);
+                frame[1 + 2 * n] = low;
+                frame[2 + 2 * n] = high;
+            }
+            self.spi.write(&frame[..1 + 2 * chunk.len()]);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use embedded_hal::spi::{ErrorType, Operation};
+
+    /// the bytes of each transfer
+    struct Log(Vec<Vec<u8>>);
+
+    impl ErrorType for Log {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiDevice for Log {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(bytes) = operation {
+                    self.0.push(bytes.to_vec());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn start_byte() {
+        let mut spi = SerialSPI::new(Log(Vec::new()));
+        spi.send(0, 0x01);
+        spi.send(RS, 0x41);
+        spi.send(_RW, 0x00);
+        // 0x01: 0001 LSB first, then 0000
+        // 0x41: 0001 LSB first, then 0100 LSB first
+        assert_eq!(
+            spi.spi.0,
+            [[0xF8, 0x80, 0x00], [0xFA, 0x80, 0x20], [0xFC, 0x00, 0x00]]
+        );
+    }
+
+    #[test]
+    fn frames_of_16_bytes() {
+        let mut spi = SerialSPI::new(Log(Vec::new()));
+        spi.send_bytes(RS, b"0123456789abcdefXY");
+        let transfers = spi.spi.0;
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].len(), 33);
+        // '0' = 0x30, 'f' = 0x66
+        assert_eq!(transfers[0][..3], [0xFA, 0x00, 0xC0]);
+        assert_eq!(transfers[0][31..], [0x60, 0x60]);
+        // 'X' = 0x58, 'Y' = 0x59
+        assert_eq!(transfers[1], [0xFA, 0x10, 0xA0, 0x90, 0xA0]);
+    }
+}
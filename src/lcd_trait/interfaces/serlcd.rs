@@ -0,0 +1,178 @@
+use super::{Interface, InterfaceKind, RS};
+use embedded_io::Write;
+
+/// prefix of the HD44780 commands
+pub const SERLCD_COMMAND: u8 = 0xFE;
+/// prefix of the setting commands
+pub const SERLCD_SETTING: u8 = 0x7C;
+
+const CREATE_CHAR: u8 = 27;
+const WRITE_CHAR: u8 = 35;
+const SET_RGB: u8 = b'+';
+const CONTRAST: u8 = 0x18;
+const TOGGLE_SPLASH: u8 = 0x09;
+const SAVE_SPLASH: u8 = 0x0A;
+const CLEAR: u8 = b'-';
+
+/// ### serial backpack (SparkFun SerLCD/OpenLCD)
+/// translates the commands to 0xFE commands, the custom characters to 0x7C commands
+/// and sends the text as is.
+/// the function set is not sent, the backpack initializes the display by itself.
+///
+/// `|` (0x7C) can't be written, the backpack reads it as a setting command
+pub struct SerLCD<W: Write> {
+    serial: W,
+    custom_char: Option<(u8, usize, [u8; 8])>,
+}
+
+impl<W: Write> SerLCD<W> {
+    pub fn new(serial: W) -> SerLCD<W> {
+        SerLCD {
+            serial,
+            custom_char: None,
+        }
+    }
+
+    /// ### sets the color of the backlight
+    pub fn set_backlight(&mut self, red: u8, green: u8, blue: u8) -> Result<(), W::Error> {
+        self.serial
+            .write_all(&[SERLCD_SETTING, SET_RGB, red, green, blue])
+    }
+
+    /// ### sets the contrast
+    pub fn set_contrast(&mut self, contrast: u8) -> Result<(), W::Error> {
+        self.serial.write_all(&[SERLCD_SETTING, CONTRAST, contrast])
+    }
+
+    /// ### saves the current text as splash screen
+    pub fn save_splash(&mut self) -> Result<(), W::Error> {
+        self.serial.write_all(&[SERLCD_SETTING, SAVE_SPLASH])
+    }
+
+    /// ### enables/disables the splash screen
+    pub fn toggle_splash(&mut self) -> Result<(), W::Error> {
+        self.serial.write_all(&[SERLCD_SETTING, TOGGLE_SPLASH])
+    }
+
+    /// ### clears the display and the settings messages
+    pub fn clear(&mut self) -> Result<(), W::Error> {
+        self.serial.write_all(&[SERLCD_SETTING, CLEAR])
+    }
+
+    fn send_data(&mut self, data: u8) {
+        if let Some((slot, len, mut bitmap)) = self.custom_char {
+            bitmap[len] = data;
+            if len + 1 == bitmap.len() {
+                self.serial.write_all(&[SERLCD_SETTING, CREATE_CHAR + slot]);
+                self.serial.write_all(&bitmap);
+                self.custom_char = None;
+            } else {
+                self.custom_char = Some((slot, len + 1, bitmap));
+            }
+        } else if data < 8 {
+            self.serial.write_all(&[SERLCD_SETTING, WRITE_CHAR + data]);
+        } else {
+            self.serial.write_all(&[data]);
+        }
+    }
+
+    fn send_command(&mut self, command: u8) {
+        if (command & 0xC7) == 0x40 {
+            // CGRAM address of a slot, the next 8 bytes are the custom character
+            self.custom_char = Some(((command >> 3) & 0x07, 0, [0; 8]));
+        } else if (command & 0xE0) != 0x20 {
+            self.custom_char = None;
+            self.serial.write_all(&[SERLCD_COMMAND, command]);
+        }
+    }
+}
+
+impl<W: Write> Interface for SerLCD<W> {
+    const KIND: InterfaceKind = InterfaceKind::Bytes;
+
+    fn send(&mut self, config: u8, data: u8) {
+        if (config & RS) != 0 {
+            self.send_data(data);
+        } else {
+            self.send_command(data);
+        }
+    }
+
+    fn send_bytes(&mut self, config: u8, data: &[u8]) {
+        if (config & RS) != 0 && self.custom_char.is_none() && data.iter().all(|byte| *byte >= 8) {
+            self.serial.write_all(data);
+        } else {
+            for byte in data {
+                self.send(config, *byte);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::emulator::VirtualDelay;
+    use crate::lcd_trait::{BusBits, LiquidCrystal, SendType, LCD16X2};
+
+    /// the bytes written to the backpack
+    struct Serial(Vec<u8>);
+
+    impl embedded_io::ErrorType for Serial {
+        type Error = core::convert::Infallible;
+    }
+
+    impl Write for Serial {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn translates_the_commands() {
+        let mut delay = VirtualDelay::default();
+        let mut serlcd = SerLCD::new(Serial(Vec::new()));
+        let mut lcd = LiquidCrystal::new(&mut serlcd, BusBits::Bus8Bits, LCD16X2);
+        lcd.begin(&mut delay);
+        lcd.write(&mut delay, SendType::Text("hi"));
+
+        // no function set (0x38)
+        assert_eq!(
+            serlcd.serial.0,
+            [0xFE, 0x01, 0xFE, 0x02, 0xFE, 0x0C, 0xFE, 0x06, b'h', b'i']
+        );
+    }
+
+    #[test]
+    fn custom_characters() {
+        let mut delay = VirtualDelay::default();
+        let mut serlcd = SerLCD::new(Serial(Vec::new()));
+        let bitmap = [0x00, 0x0A, 0x1F, 0x1F, 0x0E, 0x04, 0x00, 0x00];
+        let mut lcd = LiquidCrystal::new(&mut serlcd, BusBits::Bus8Bits, LCD16X2);
+        lcd.custom_char(&mut delay, &bitmap, 2);
+        lcd.write(&mut delay, SendType::CustomChar(2));
+        lcd.write(&mut delay, SendType::Text("a"));
+
+        let mut expected = vec![SERLCD_SETTING, 27 + 2];
+        expected.extend_from_slice(&bitmap);
+        expected.extend_from_slice(&[0xFE, 0x02, SERLCD_SETTING, 35 + 2, b'a']);
+        assert_eq!(serlcd.serial.0, expected);
+    }
+
+    #[test]
+    fn settings() {
+        let mut serlcd = SerLCD::new(Serial(Vec::new()));
+        serlcd.set_backlight(255, 0, 128).unwrap();
+        serlcd.set_contrast(40).unwrap();
+        serlcd.clear().unwrap();
+        assert_eq!(
+            serlcd.serial.0,
+            [0x7C, b'+', 255, 0, 128, 0x7C, 0x18, 40, 0x7C, b'-']
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use super::{bit, Interface, PinMap, EN, NC};
+use embedded_hal::digital::OutputPin;
+
+/// ShiftRegLCD 2 wires wiring: EN (diode-AND) on Q7, RS on Q6, D7 - D4 on Q5 - Q2, Q1 and Q0 not used
+pub const SHIFT_REG_2W: PinMap = PinMap {
+    rs: 6,
+    rw: NC,
+    en: 7,
+    en2: NC,
+    data: [NC, NC, NC, NC, 2, 3, 4, 5],
+};
+
+/// shifts `len` bits of `word` MSB first (the first bit ends on the last output)
+fn shift_out<D: OutputPin, C: OutputPin>(data: &mut D, clock: &mut C, word: u16, len: u8) {
+    for n in (0..len).rev() {
+        if word & (1 << n) != 0 {
+            data.set_high();
+        } else {
+            data.set_low();
+        }
+        clock.set_high();
+        clock.set_low();
+    }
+}
+
+/// ### 2 wires shift register (74HC164 or 74HC595 with latch tied to clock)
+/// the output mapped as `en` is the gate of the diode-AND with the data line:
+/// the register is cleared and loaded with the gate high while the data line is low,
+/// then the data line generates the enable pulse.
+///
+/// the last output (Q0) must not be used, it receives the last bit while the gate is already high.
+pub struct ShiftRegister2W<D: OutputPin, C: OutputPin> {
+    data: D,
+    clock: C,
+    pins: PinMap,
+    backlight_pin: u8,
+    backlight: bool,
+    register: Option<u16>,
+}
+
+impl<D: OutputPin, C: OutputPin> ShiftRegister2W<D, C> {
+    /// `backlight` is the output of the backlight, or `NC`
+    pub fn new(data: D, clock: C, pins: PinMap, backlight: u8) -> ShiftRegister2W<D, C> {
+        ShiftRegister2W {
+            data,
+            clock,
+            pins,
+            backlight_pin: backlight,
+            backlight: true,
+            register: None,
+        }
+    }
+
+    /// ### turns the backlight on or off
+    /// applied on the next send
+    pub fn set_backlight(&mut self, state: bool) {
+        self.backlight = state;
+    }
+}
+
+impl<D: OutputPin, C: OutputPin> Interface for ShiftRegister2W<D, C> {
+    fn send(&mut self, config: u8, data: u8) {
+        if config & (EN | EN << 1) != 0 {
+            self.data.set_high();
+            return;
+        }
+        self.data.set_low();
+        let word = self.pins.pack(config | EN, data) | bit(self.backlight_pin, self.backlight);
+        if self.register != Some(word) {
+            shift_out(&mut self.data, &mut self.clock, 0, 8);
+            shift_out(&mut self.data, &mut self.clock, word, 8);
+            self.data.set_low();
+            self.register = Some(word);
+        }
+    }
+}
+
+/// ### 3 wires shift register (74HC595 with data, clock and latch)
+/// works like the `HC595` interface, but with the register driven by pins.
+/// `pins` is any `PinMap`, a chain of two registers is used if an output above 7 is mapped
+pub struct ShiftRegister3W<D: OutputPin, C: OutputPin, L: OutputPin> {
+    data: D,
+    clock: C,
+    latch: L,
+    pins: PinMap,
+    backlight_pin: u8,
+    backlight: bool,
+}
+
+impl<D: OutputPin, C: OutputPin, L: OutputPin> ShiftRegister3W<D, C, L> {
+    /// `backlight` is the output of the backlight, or `NC`
+    pub fn new(
+        data: D,
+        clock: C,
+        latch: L,
+        pins: PinMap,
+        backlight: u8,
+    ) -> ShiftRegister3W<D, C, L> {
+        ShiftRegister3W {
+            data,
+            clock,
+            latch,
+            pins,
+            backlight_pin: backlight,
+            backlight: true,
+        }
+    }
+
+    /// ### turns the backlight on or off
+    /// applied on the next send
+    pub fn set_backlight(&mut self, state: bool) {
+        self.backlight = state;
+    }
+}
+
+impl<D: OutputPin, C: OutputPin, L: OutputPin> Interface for ShiftRegister3W<D, C, L> {
+    fn send(&mut self, config: u8, data: u8) {
+        let word = self.pins.pack(config, data) | bit(self.backlight_pin, self.backlight);
+        let len = if (self.pins.mask() | bit(self.backlight_pin, true)) > 0xFF {
+            16
+        } else {
+            8
+        };
+        self.latch.set_low();
+        shift_out(&mut self.data, &mut self.clock, word, len);
+        self.latch.set_high();
+    }
+}
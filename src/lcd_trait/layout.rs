@@ -1,3 +1,4 @@
+#[derive(Clone, Copy)]
 pub struct Layout<const COLS: u8, const LINES: usize> {
     pub addrs: [u8; LINES],
 }
@@ -9,3 +10,28 @@ pub const LCD16X2: Layout<16, 2> = Layout {
 pub const LCD20X4: Layout<20, 4> = Layout {
     addrs: [0x80, 0xC0, 0x80 + 20, 0xC0 + 20],
 };
+
+/// 4 lines addressing mode (US2066, SSD1803A, KS0073...)
+pub const LCD20X4_4LINE: Layout<20, 4> = Layout {
+    addrs: [0x80, 0xA0, 0xC0, 0xE0],
+};
+
+/// 3 lines with the first line in double height (SSD1803A `DoubleHeight::Top`)
+pub const LCD20X3_DOUBLE_TOP: Layout<20, 3> = Layout {
+    addrs: [0x80, 0xC0, 0xE0],
+};
+
+/// 3 lines with the second line in double height (SSD1803A `DoubleHeight::Middle`)
+pub const LCD20X3_DOUBLE_MIDDLE: Layout<20, 3> = Layout {
+    addrs: [0x80, 0xA0, 0xE0],
+};
+
+/// 3 lines with the last line in double height (SSD1803A `DoubleHeight::Bottom`)
+pub const LCD20X3_DOUBLE_BOTTOM: Layout<20, 3> = Layout {
+    addrs: [0x80, 0xA0, 0xC0],
+};
+
+/// 2 lines in double height (SSD1803A `DoubleHeight::TopBottom`)
+pub const LCD20X2_DOUBLE: Layout<20, 2> = Layout {
+    addrs: [0x80, 0xC0],
+};
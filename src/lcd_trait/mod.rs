@@ -7,9 +7,11 @@ pub use embedded_hal_async::delay::DelayNs as ADelay;
 pub mod commands;
 pub mod interfaces;
 pub mod layout;
+pub mod profiles;
 
 pub use interfaces::*;
 pub use layout::*;
+pub use profiles::*;
 
 pub use commands::Commands::*;
 pub use commands::*;
@@ -21,16 +23,19 @@ pub enum SendType<'s> {
     CustomChar(u8),
 }
 
+#[derive(Clone, Copy)]
 pub enum BusBits {
     Bus4Bits,
     Bus8Bits,
 }
 
+#[allow(clippy::enum_variant_names)]
 enum LCDEntryMode {
     LCDShiftMode = 0x01,
     LCDDirection = 0x02,
 }
 
+#[allow(clippy::enum_variant_names)]
 enum LCDDisplayControl {
     LCDBlink = 0x01,
     LCDCursor = 0x02,
@@ -105,33 +110,56 @@ impl<'interface, T: Interface, const COLS: u8, const LINES: usize>
     /// (0x01 write)
     /// (0x00 command)
     pub fn send(&mut self, delay: &mut impl DelayNs, data: u8, rs_state: u8) {
-        match self.bus {
-            BusBits::Bus8Bits => self.send8bits(delay, data, rs_state),
-            BusBits::Bus4Bits => self.send4bits(delay, data, rs_state),
-        };
-
-        if rs_state == 1 {
-            delay.delay_us(2);
+        if T::KIND == InterfaceKind::Bytes {
+            self.interface.send(rs_state, data);
         } else {
-            delay.delay_us(40);
+            match self.bus {
+                BusBits::Bus8Bits => self.send8bits(delay, data, rs_state),
+                BusBits::Bus4Bits => self.send4bits(delay, data, rs_state),
+            };
+        }
+
+        delay.delay_us(40); //execution time of the instructions and the writes (37 µs)
+    }
+
+    /// sends the text in data frames, only used by `Bytes` interfaces
+    fn send_frames(&mut self, delay: &mut impl DelayNs, text: &str) {
+        let mut frame = [0; 16];
+        let mut chars = text.chars();
+        loop {
+            let mut len = 0;
+            for (byte, text) in frame.iter_mut().zip(chars.by_ref()) {
+                *byte = text as u8;
+                len += 1;
+            }
+            if len == 0 {
+                break;
+            }
+            self.interface.send_bytes(RS, &frame[..len]);
+            delay.delay_us(40 * len as u32);
         }
     }
 
     pub fn begin(&mut self, delay: &mut impl DelayNs) {
         delay.delay_ms(50);
-        self.send8bits(delay, 0x30, 0);
-        delay.delay_us(4100);
-        self.send8bits(delay, 0x30, 0);
-        delay.delay_us(100);
-        self.send8bits(delay, 0x30, 0);
-        delay.delay_us(100);
-        match self.bus {
-            BusBits::Bus8Bits => self.send8bits(delay, 0x38, 0),
-            BusBits::Bus4Bits => {
-                self.send8bits(delay, 0x20, 0);
-                self.send(delay, 0x28, 0);
-            }
-        };
+        if T::KIND == InterfaceKind::Bytes {
+            self.send(delay, 0x38, 0);
+        } else {
+            self.send8bits(delay, 0x30, 0);
+            delay.delay_us(4100);
+            self.send8bits(delay, 0x30, 0);
+            delay.delay_us(100);
+            self.send8bits(delay, 0x30, 0);
+            delay.delay_us(100);
+            match self.bus {
+                BusBits::Bus8Bits => self.send(delay, 0x38, 0),
+                BusBits::Bus4Bits => {
+                    self.send8bits(delay, 0x20, 0);
+                    delay.delay_us(40);
+                    self.send(delay, 0x28, 0);
+                }
+            };
+        }
         self.write(delay, SendType::Command(Clear));
         self.write(delay, SendType::Command(Reset));
         self.update_config(delay);
@@ -162,8 +190,12 @@ impl<'interface, T: Interface, const COLS: u8, const LINES: usize>
                 delay.delay_us(2000);
             }
             SendType::Text(x) => {
-                for text in x.chars() {
-                    self.send(delay, text as u8, RS);
+                if T::KIND == InterfaceKind::Bytes {
+                    self.send_frames(delay, x);
+                } else {
+                    for text in x.chars() {
+                        self.send(delay, text as u8, RS);
+                    }
                 }
             }
             SendType::CustomChar(slot) => {
@@ -194,8 +226,8 @@ impl<'interface, T: Interface, const COLS: u8, const LINES: usize>
     ) -> &mut Self {
         if slot < 8 {
             self.send(delay, 0x40 | (slot << 3), 0x00);
-            for c in 0..8 {
-                self.send(delay, char_array[c], RS);
+            for c in char_array {
+                self.send(delay, *c, RS);
             }
         }
         self.write(delay, SendType::Command(Reset));
@@ -248,33 +280,56 @@ impl<'interface, T: Interface, const COLS: u8, const LINES: usize>
     /// (0x01 write)
     /// (0x00 command)
     pub async fn send(&mut self, delay: &mut impl ADelay, data: u8, rs_state: u8) {
-        match self.bus {
-            BusBits::Bus8Bits => self.send8bits(delay, data, rs_state).await,
-            BusBits::Bus4Bits => self.send4bits(delay, data, rs_state).await,
-        };
-
-        if rs_state == 1 {
-            delay.delay_us(2).await;
+        if T::KIND == InterfaceKind::Bytes {
+            self.interface.send(rs_state, data);
         } else {
-            delay.delay_us(40).await;
+            match self.bus {
+                BusBits::Bus8Bits => self.send8bits(delay, data, rs_state).await,
+                BusBits::Bus4Bits => self.send4bits(delay, data, rs_state).await,
+            };
+        }
+
+        delay.delay_us(40).await; //execution time of the instructions and the writes (37 µs)
+    }
+
+    /// sends the text in data frames, only used by `Bytes` interfaces
+    async fn send_frames(&mut self, delay: &mut impl ADelay, text: &str) {
+        let mut frame = [0; 16];
+        let mut chars = text.chars();
+        loop {
+            let mut len = 0;
+            for (byte, text) in frame.iter_mut().zip(chars.by_ref()) {
+                *byte = text as u8;
+                len += 1;
+            }
+            if len == 0 {
+                break;
+            }
+            self.interface.send_bytes(RS, &frame[..len]);
+            delay.delay_us(40 * len as u32).await;
         }
     }
 
     pub async fn begin(&mut self, delay: &mut impl ADelay) {
         delay.delay_ms(50).await;
-        self.send8bits(delay, 0x30, 0).await;
-        delay.delay_us(4100).await;
-        self.send8bits(delay, 0x30, 0).await;
-        delay.delay_us(100).await;
-        self.send8bits(delay, 0x30, 0).await;
-        delay.delay_us(100).await;
-        match self.bus {
-            BusBits::Bus8Bits => self.send8bits(delay, 0x38, 0).await,
-            BusBits::Bus4Bits => {
-                self.send8bits(delay, 0x20, 0).await;
-                self.send(delay, 0x28, 0).await;
-            }
-        };
+        if T::KIND == InterfaceKind::Bytes {
+            self.send(delay, 0x38, 0).await;
+        } else {
+            self.send8bits(delay, 0x30, 0).await;
+            delay.delay_us(4100).await;
+            self.send8bits(delay, 0x30, 0).await;
+            delay.delay_us(100).await;
+            self.send8bits(delay, 0x30, 0).await;
+            delay.delay_us(100).await;
+            match self.bus {
+                BusBits::Bus8Bits => self.send(delay, 0x38, 0).await,
+                BusBits::Bus4Bits => {
+                    self.send8bits(delay, 0x20, 0).await;
+                    delay.delay_us(40).await;
+                    self.send(delay, 0x28, 0).await;
+                }
+            };
+        }
         self.write(delay, SendType::Command(Clear)).await;
         self.write(delay, SendType::Command(Reset)).await;
         self.update_config(delay).await;
@@ -305,8 +360,12 @@ impl<'interface, T: Interface, const COLS: u8, const LINES: usize>
                 delay.delay_us(2000).await;
             }
             SendType::Text(x) => {
-                for text in x.chars() {
-                    self.send(delay, text as u8, RS).await;
+                if T::KIND == InterfaceKind::Bytes {
+                    self.send_frames(delay, x).await;
+                } else {
+                    for text in x.chars() {
+                        self.send(delay, text as u8, RS).await;
+                    }
                 }
             }
             SendType::CustomChar(slot) => {
@@ -337,8 +396,8 @@ impl<'interface, T: Interface, const COLS: u8, const LINES: usize>
     ) -> &mut Self {
         if slot < 8 {
             self.send(delay, 0x40 | (slot << 3), 0x00).await;
-            for c in 0..8 {
-                self.send(delay, char_array[c], RS).await;
+            for c in char_array {
+                self.send(delay, *c, RS).await;
             }
         }
         self.write(delay, SendType::Command(Reset)).await;
@@ -353,9 +412,136 @@ impl<'interface, T: Interface, const COLS: u8, const LINES: usize>
     }
 }
 
+impl<'interface, T: ReadInterface, const COLS: u8, const LINES: usize>
+    LiquidCrystal<'interface, T, COLS, LINES>
+{
+    fn read8bits(&mut self, delay: &mut impl DelayNs, rs_state: u8) -> u8 {
+        self.interface.send(rs_state | _RW, 0);
+        self.interface
+            .send(rs_state | _RW | (self.corrent_enable << 2), 0);
+        delay.delay_us(1);
+        let data = self.interface.read();
+        self.interface.send(rs_state | _RW, 0);
+        data
+    }
+
+    fn read4bits(&mut self, delay: &mut impl DelayNs, rs_state: u8) -> u8 {
+        let high_nibble = self.read8bits(delay, rs_state) & 0xF0;
+        delay.delay_us(1);
+        let low_nibble = self.read8bits(delay, rs_state) >> 4;
+        high_nibble | low_nibble
+    }
+
+    /// ### low level function to read data.
+    /// `rs_state` represents the state of the RS pin of the display
+    /// (0x01 read data from the DDRAM/CGRAM)
+    /// (0x00 read busy flag and address)
+    ///
+    /// select only one display before reading!
+    pub fn receive(&mut self, delay: &mut impl DelayNs, rs_state: u8) -> u8 {
+        let data = match self.bus {
+            BusBits::Bus8Bits => self.read8bits(delay, rs_state),
+            BusBits::Bus4Bits => self.read4bits(delay, rs_state),
+        };
+        if rs_state == RS {
+            delay.delay_us(40); //the read of the RAM moves the address counter
+        } else {
+            delay.delay_us(2);
+        }
+        data
+    }
+
+    /// ### returns true while the display is busy
+    pub fn read_busy_flag(&mut self, delay: &mut impl DelayNs) -> bool {
+        (self.receive(delay, 0) & 0x80) != 0
+    }
+
+    /// ### reads the address counter (cursor position)
+    pub fn read_address(&mut self, delay: &mut impl DelayNs) -> u8 {
+        self.receive(delay, 0) & 0x7F
+    }
+
+    /// ### reads the DDRAM/CGRAM at the cursor position
+    /// the cursor moves like a write
+    pub fn read_data(&mut self, delay: &mut impl DelayNs) -> u8 {
+        self.receive(delay, RS)
+    }
+}
+
+#[cfg(feature="async")]
+impl<'interface, T: ReadInterface, const COLS: u8, const LINES: usize>
+    LiquidCrystal<'interface, T, COLS, LINES, Async>
+{
+    async fn read8bits(&mut self, delay: &mut impl ADelay, rs_state: u8) -> u8 {
+        self.interface.send(rs_state | _RW, 0);
+        self.interface
+            .send(rs_state | _RW | (self.corrent_enable << 2), 0);
+        delay.delay_us(1).await;
+        let data = self.interface.read();
+        self.interface.send(rs_state | _RW, 0);
+        data
+    }
+
+    async fn read4bits(&mut self, delay: &mut impl ADelay, rs_state: u8) -> u8 {
+        let high_nibble = self.read8bits(delay, rs_state).await & 0xF0;
+        delay.delay_us(1).await;
+        let low_nibble = self.read8bits(delay, rs_state).await >> 4;
+        high_nibble | low_nibble
+    }
+
+    /// ### low level function to read data.
+    /// `rs_state` represents the state of the RS pin of the display
+    /// (0x01 read data from the DDRAM/CGRAM)
+    /// (0x00 read busy flag and address)
+    ///
+    /// select only one display before reading!
+    pub async fn receive(&mut self, delay: &mut impl ADelay, rs_state: u8) -> u8 {
+        let data = match self.bus {
+            BusBits::Bus8Bits => self.read8bits(delay, rs_state).await,
+            BusBits::Bus4Bits => self.read4bits(delay, rs_state).await,
+        };
+        if rs_state == RS {
+            delay.delay_us(40).await; //the read of the RAM moves the address counter
+        } else {
+            delay.delay_us(2).await;
+        }
+        data
+    }
+
+    /// ### returns true while the display is busy
+    pub async fn read_busy_flag(&mut self, delay: &mut impl ADelay) -> bool {
+        (self.receive(delay, 0).await & 0x80) != 0
+    }
+
+    /// ### reads the address counter (cursor position)
+    pub async fn read_address(&mut self, delay: &mut impl ADelay) -> u8 {
+        self.receive(delay, 0).await & 0x7F
+    }
+
+    /// ### reads the DDRAM/CGRAM at the cursor position
+    /// the cursor moves like a write
+    pub async fn read_data(&mut self, delay: &mut impl ADelay) -> u8 {
+        self.receive(delay, RS).await
+    }
+}
+
 impl<'interface, T: Interface, const COLS: u8, const LINES: usize, MODE>
     LiquidCrystal<'interface, T, COLS, LINES, MODE>
 {
+    /// ### access the interface
+    /// useful for interface specific functions (backlight, buttons...)
+    pub fn interface(&mut self) -> &mut T {
+        self.interface
+    }
+
+    /// function set of the current bus, with 2 lines
+    fn function_set(&self) -> u8 {
+        match (T::KIND, &self.bus) {
+            (InterfaceKind::Pins, BusBits::Bus4Bits) => 0x28,
+            _ => 0x38,
+        }
+    }
+
     /// ### enable all displays
     pub fn echo(&mut self) -> &mut Self {
         self.corrent_enable = 0b11;
@@ -0,0 +1,174 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::lcd_trait::{DelayNs, DoubleHeight, Interface, LiquidCrystal};
+
+const EXTENDED_FUNCTION_SET: u8 = 0x08;
+const FOUR_LINES: u8 = 0x01;
+const DOUBLE_HEIGHT: u8 = 0x10;
+const SCROLL_ENABLE: u8 = 0x10;
+const SCROLL_QUANTITY: u8 = 0x80;
+
+/// ### controllers with the RE extension bit
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ExtendedController {
+    SSD1803A,
+    KS0073,
+}
+
+impl ExtendedController {
+    /// bit RE of the function set
+    fn re(self) -> u8 {
+        match self {
+            ExtendedController::SSD1803A => 0x02,
+            ExtendedController::KS0073 => 0x04,
+        }
+    }
+
+    /// bit DH of the function set (RE = 0)
+    fn dh(self) -> u8 {
+        match self {
+            ExtendedController::SSD1803A => 0x04,
+            ExtendedController::KS0073 => 0x02,
+        }
+    }
+}
+
+/// ### SSD1803A / KS0073 profile
+/// 4 lines addressing, 3 lines and double height (SSD1803A) and per line horizontal scroll.
+/// works over parallel interfaces and `SerialSPI`.
+///
+/// use `LCD20X4_4LINE` for 4 lines, and the layout of the double height mode for 3 lines
+/// (`LCD20X3_DOUBLE_TOP`, `LCD20X3_DOUBLE_MIDDLE`, `LCD20X3_DOUBLE_BOTTOM` or `LCD20X2_DOUBLE`)
+///
+/// ```ignore
+/// let mut lcd = ExtendedLCD::new(LiquidCrystal::new(&mut interface, Bus4Bits, LCD20X4_4LINE), ExtendedController::KS0073);
+/// lcd.begin(&mut delay);
+/// lcd.set_cursor(&mut delay, 3, 0).write(&mut delay, Text("line 4"));
+/// ```
+pub struct ExtendedLCD<'interface, T: Interface, const COLS: u8, const LINES: usize> {
+    lcd: LiquidCrystal<'interface, T, COLS, LINES>,
+    controller: ExtendedController,
+    double_height: bool,
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize>
+    ExtendedLCD<'interface, T, COLS, LINES>
+{
+    pub fn new(
+        lcd: LiquidCrystal<'interface, T, COLS, LINES>,
+        controller: ExtendedController,
+    ) -> ExtendedLCD<'interface, T, COLS, LINES> {
+        ExtendedLCD {
+            lcd,
+            controller,
+            double_height: false,
+        }
+    }
+
+    /// ### returns the `LiquidCrystal`
+    pub fn release(self) -> LiquidCrystal<'interface, T, COLS, LINES> {
+        self.lcd
+    }
+
+    /// ### initialization sequence
+    /// selects the 4 lines mode if the layout has more than 2 lines
+    pub fn begin(&mut self, delay: &mut impl DelayNs) -> &mut Self {
+        self.lcd.begin(delay);
+        self.double_height = false;
+        let lines = if LINES > 2 { FOUR_LINES } else { 0x00 };
+        self.extended(delay, false, |lcd, delay| {
+            lcd.send(delay, EXTENDED_FUNCTION_SET | lines, 0);
+        });
+        self
+    }
+
+    /// ### enables double height
+    /// on the SSD1803A `lines` selects the lines in double height (3 lines mode),
+    /// the KS0073 ignores `lines`
+    pub fn enable_double_height(
+        &mut self,
+        delay: &mut impl DelayNs,
+        lines: DoubleHeight,
+    ) -> &mut Self {
+        self.double_height = true;
+        if self.controller == ExtendedController::SSD1803A {
+            self.extended(delay, false, |lcd, delay| {
+                lcd.send(delay, DOUBLE_HEIGHT | lines as u8, 0);
+            });
+        } else {
+            let function_set = self.function_set();
+            self.lcd.send(delay, function_set, 0);
+        }
+        self
+    }
+
+    /// ### all lines in normal height
+    pub fn disable_double_height(&mut self, delay: &mut impl DelayNs) -> &mut Self {
+        self.double_height = false;
+        let function_set = self.function_set();
+        self.lcd.send(delay, function_set, 0);
+        self
+    }
+
+    /// ### enables the horizontal scroll of each line
+    /// bit 0 = line 1 ... bit 3 = line 4, the lines are moved by `set_scroll_quantity`
+    pub fn set_scroll_lines(&mut self, delay: &mut impl DelayNs, lines: u8) -> &mut Self {
+        self.extended(delay, true, |lcd, delay| {
+            lcd.send(delay, SCROLL_ENABLE | (lines & 0x0F), 0);
+        });
+        self
+    }
+
+    /// ### scrolls the enabled lines by `dots` (0 - 63)
+    pub fn set_scroll_quantity(&mut self, delay: &mut impl DelayNs, dots: u8) -> &mut Self {
+        self.extended(delay, true, |lcd, delay| {
+            lcd.send(delay, SCROLL_QUANTITY | (dots & 0x3F), 0);
+        });
+        self
+    }
+
+    /// function set with RE = 0
+    fn function_set(&self) -> u8 {
+        if self.double_height {
+            self.lcd.function_set() | self.controller.dh()
+        } else {
+            self.lcd.function_set()
+        }
+    }
+
+    /// runs `f` with the extended function set (RE = 1),
+    /// `scroll` selects the scroll instructions of the SSD1803A (IS = 1)
+    fn extended<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        scroll: bool,
+        f: impl FnOnce(&mut LiquidCrystal<'interface, T, COLS, LINES>, &mut D),
+    ) {
+        let function_set = self.function_set();
+        if scroll && self.controller == ExtendedController::SSD1803A {
+            self.lcd.send(delay, function_set | 0x01, 0); //IS = 1
+        }
+        self.lcd
+            .send(delay, self.lcd.function_set() | self.controller.re(), 0);
+        f(&mut self.lcd, delay);
+        self.lcd.send(delay, function_set, 0);
+    }
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize> Deref
+    for ExtendedLCD<'interface, T, COLS, LINES>
+{
+    type Target = LiquidCrystal<'interface, T, COLS, LINES>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.lcd
+    }
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize> DerefMut
+    for ExtendedLCD<'interface, T, COLS, LINES>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.lcd
+    }
+}
@@ -0,0 +1,30 @@
+//! ### controller profiles
+//! controllers compatible with the HD44780 that need a different initialization
+//! or have extra functions.
+//!
+//! each profile owns the `LiquidCrystal` and dereferences to it,
+//! so all functions of `LiquidCrystal` are still available.
+
+pub mod extended;
+pub mod st7032;
+pub mod us2066;
+pub mod vfd;
+pub mod ws0010;
+
+pub use extended::*;
+pub use st7032::*;
+pub use us2066::*;
+pub use vfd::*;
+pub use ws0010::*;
+
+/// ### lines in double height
+/// with 4 lines: `Top` shows the lines 1 (double), 3 and 4, `Middle` 1, 2 (double) and 4,
+/// `Bottom` 1, 2 and 3 (double), `TopBottom` 1 (double) and 3 (double).
+/// with 2 lines any mode shows a single double line
+#[derive(Clone, Copy)]
+pub enum DoubleHeight {
+    Top = 0b0000,
+    Middle = 0b0100,
+    Bottom = 0b1000,
+    TopBottom = 0b1100,
+}
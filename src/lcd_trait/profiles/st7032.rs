@@ -0,0 +1,244 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::lcd_trait::{DelayNs, Interface, LiquidCrystal, RS};
+
+/// instruction table 1 (extended instructions)
+const IS: u8 = 0x01;
+
+const INTERNAL_OSC: u8 = 0x14; //bias 1/4 (BS = 1), frequency 183 Hz
+const ICON_ADDRESS: u8 = 0x40;
+const POWER_ICON_CONTRAST: u8 = 0x50;
+const FOLLOWER: u8 = 0x60;
+const CONTRAST_LOW: u8 = 0x70;
+
+const ICON_ON: u8 = 0x08;
+const BOOSTER_ON: u8 = 0x04;
+const FOLLOWER_ON: u8 = 0x08;
+
+/// ### ST7032 profile
+/// software contrast, booster, voltage follower and icon RAM
+/// of the ST7032 (3.3V COG displays without contrast pot).
+/// works with `NativeI2C` or parallel interfaces
+///
+/// ```ignore
+/// let mut lcd = ST7032::new(LiquidCrystal::new(&mut interface, Bus8Bits, LCD16X2));
+/// lcd.begin(&mut delay);
+/// lcd.set_contrast(&mut delay, 40);
+/// lcd.write(&mut delay, Text("hello World!"));
+/// ```
+pub struct ST7032<'interface, T: Interface, const COLS: u8, const LINES: usize> {
+    lcd: LiquidCrystal<'interface, T, COLS, LINES>,
+    contrast: u8,
+    booster: bool,
+    follower: bool,
+    follower_ratio: u8,
+    icons: bool,
+    icon_ram: [u8; 16],
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize>
+    ST7032<'interface, T, COLS, LINES>
+{
+    /// default: contrast 35, booster on (3.3V), follower on with ratio 4
+    pub fn new(
+        lcd: LiquidCrystal<'interface, T, COLS, LINES>,
+    ) -> ST7032<'interface, T, COLS, LINES> {
+        ST7032 {
+            lcd,
+            contrast: 35,
+            booster: true,
+            follower: true,
+            follower_ratio: 4,
+            icons: false,
+            icon_ram: [0; 16],
+        }
+    }
+
+    /// ### returns the `LiquidCrystal`
+    pub fn release(self) -> LiquidCrystal<'interface, T, COLS, LINES> {
+        self.lcd
+    }
+
+    /// ### initialization sequence of the ST7032
+    /// (waits 200 ms for the voltage follower)
+    pub fn begin(&mut self, delay: &mut impl DelayNs) -> &mut Self {
+        self.lcd.begin(delay);
+        self.extended(delay, |lcd, delay| {
+            lcd.send(delay, INTERNAL_OSC, 0);
+        });
+        self.apply_power(delay);
+        delay.delay_ms(200);
+        self.lcd.update_config(delay);
+        self
+    }
+
+    /// ### sets the contrast (0 - 63)
+    pub fn set_contrast(&mut self, delay: &mut impl DelayNs, contrast: u8) -> &mut Self {
+        self.contrast = contrast & 0x3F;
+        self.apply_power(delay);
+        self
+    }
+
+    /// ### turns the booster on (3.3V) or off (5V)
+    pub fn set_booster(&mut self, delay: &mut impl DelayNs, state: bool) -> &mut Self {
+        self.booster = state;
+        self.apply_power(delay);
+        self
+    }
+
+    /// ### turns the voltage follower on or off
+    /// `ratio` (0 - 7) is the amplifier ratio of the follower
+    pub fn set_follower(&mut self, delay: &mut impl DelayNs, state: bool, ratio: u8) -> &mut Self {
+        self.follower = state;
+        self.follower_ratio = ratio & 0x07;
+        self.apply_power(delay);
+        self
+    }
+
+    /// ### shows or hides the icons
+    pub fn set_icons(&mut self, delay: &mut impl DelayNs, state: bool) -> &mut Self {
+        self.icons = state;
+        self.apply_power(delay);
+        self
+    }
+
+    /// ### writes an address of the icon RAM (0 - 15)
+    /// each address holds 5 icons (bits 0 - 4), the position of the icons depends on the glass.
+    /// use `set_cursor` after writing icons
+    pub fn set_icon(&mut self, delay: &mut impl DelayNs, addr: u8, bits: u8) -> &mut Self {
+        let addr = addr & 0x0F;
+        self.icon_ram[addr as usize] = bits & 0x1F;
+        self.extended(delay, |lcd, delay| {
+            lcd.send(delay, ICON_ADDRESS | addr, 0);
+            lcd.send(delay, bits & 0x1F, RS);
+        });
+        self
+    }
+
+    /// ### turns one icon on or off
+    /// `bit` (0 - 4) is the icon in the address `addr`
+    pub fn set_icon_bit(
+        &mut self,
+        delay: &mut impl DelayNs,
+        addr: u8,
+        bit: u8,
+        state: bool,
+    ) -> &mut Self {
+        debug_assert!(bit < 5, "the icons are the bits 0 - 4");
+        let mask = 1u8.checked_shl(bit as u32).unwrap_or(0) & 0x1F;
+        let mut bits = self.icon_ram[(addr & 0x0F) as usize];
+        if state {
+            bits |= mask;
+        } else {
+            bits &= !mask;
+        }
+        self.set_icon(delay, addr, bits)
+    }
+
+    /// ### turns all icons off
+    pub fn clear_icons(&mut self, delay: &mut impl DelayNs) -> &mut Self {
+        for addr in 0..16 {
+            self.set_icon(delay, addr, 0);
+        }
+        self
+    }
+
+    /// sends the power, contrast and follower settings
+    fn apply_power(&mut self, delay: &mut impl DelayNs) {
+        let mut power = POWER_ICON_CONTRAST | (self.contrast >> 4);
+        if self.icons {
+            power |= ICON_ON;
+        }
+        if self.booster {
+            power |= BOOSTER_ON;
+        }
+        let mut follower = FOLLOWER | self.follower_ratio;
+        if self.follower {
+            follower |= FOLLOWER_ON;
+        }
+        let contrast = CONTRAST_LOW | (self.contrast & 0x0F);
+        self.extended(delay, |lcd, delay| {
+            lcd.send(delay, contrast, 0);
+            lcd.send(delay, power, 0);
+            lcd.send(delay, follower, 0);
+        });
+    }
+
+    /// runs `f` with the extended instruction table (IS = 1)
+    fn extended<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        f: impl FnOnce(&mut LiquidCrystal<'interface, T, COLS, LINES>, &mut D),
+    ) {
+        let function_set = self.lcd.function_set();
+        self.lcd.send(delay, function_set | IS, 0);
+        f(&mut self.lcd, delay);
+        self.lcd.send(delay, function_set, 0);
+    }
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize> Deref
+    for ST7032<'interface, T, COLS, LINES>
+{
+    type Target = LiquidCrystal<'interface, T, COLS, LINES>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.lcd
+    }
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize> DerefMut
+    for ST7032<'interface, T, COLS, LINES>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.lcd
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::emulator::{MockInterface, VirtualDelay};
+    use crate::lcd_trait::{BusBits, LCD16X2};
+
+    #[test]
+    fn begin_sequence() {
+        let mut delay = VirtualDelay::default();
+        let mut mock = MockInterface::new();
+        ST7032::new(LiquidCrystal::new(&mut mock, BusBits::Bus8Bits, LCD16X2)).begin(&mut delay);
+
+        mock.expect_commands(&[
+            0x30, 0x30, 0x30, 0x38, // reset sequence and function set
+            0x01, 0x02, 0x0C, 0x06, // LiquidCrystal::begin
+            0x39, 0x14, 0x38, // bias and oscillator
+            0x39, 0x73, 0x56, 0x6C, 0x38, // contrast 35, booster, follower ratio 4
+            0x0C, 0x06,
+        ]);
+        // 200 ms for the follower
+        assert!(delay.clock().now() > 250_000_000);
+    }
+
+    #[test]
+    fn icons() {
+        let mut delay = VirtualDelay::default();
+        let mut mock = MockInterface::new();
+        let mut lcd = ST7032::new(LiquidCrystal::new(&mut mock, BusBits::Bus4Bits, LCD16X2));
+        lcd.begin(&mut delay);
+        lcd.interface().clear();
+        lcd.set_icon_bit(&mut delay, 3, 4, true);
+        lcd.set_icon_bit(&mut delay, 3, 0, true);
+        lcd.set_icon_bit(&mut delay, 3, 4, false);
+
+        mock.expect_commands(&[0x29, 0x43, 0x28, 0x29, 0x43, 0x28, 0x29, 0x43, 0x28]);
+        assert_eq!(mock.data(), [0x10, 0x11, 0x01]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the icons are the bits 0 - 4")]
+    fn icon_bit_out_of_range() {
+        let mut delay = VirtualDelay::default();
+        let mut mock = MockInterface::new();
+        let mut lcd = ST7032::new(LiquidCrystal::new(&mut mock, BusBits::Bus4Bits, LCD16X2));
+        lcd.set_icon_bit(&mut delay, 0, 8, true);
+    }
+}
@@ -0,0 +1,273 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::lcd_trait::{Clear, DelayNs, DoubleHeight, Interface, LiquidCrystal, SendType, RS};
+
+/// extended function set register (RE = 1)
+const RE: u8 = 0x02;
+/// double height (function set with RE = 0)
+const DH: u8 = 0x04;
+
+const FUNCTION_SELECTION_A: u8 = 0x71;
+const FUNCTION_SELECTION_B: u8 = 0x72;
+const OLED_COMMANDS_ON: u8 = 0x79;
+const OLED_COMMANDS_OFF: u8 = 0x78;
+const EXTENDED_FUNCTION_SET: u8 = 0x08;
+const FOUR_LINES: u8 = 0x01;
+const DOUBLE_HEIGHT: u8 = 0x10;
+const ENTRY_MODE_COM_SEG: u8 = 0x06; //COM0 -> COM31, SEG99 -> SEG0
+
+const CONTRAST: u8 = 0x81;
+const CLOCK_DIVIDE: u8 = 0xD5;
+const SEG_PINS: u8 = 0xDA;
+const FUNCTION_SELECTION_C: u8 = 0xDC;
+const PHASE_LENGTH: u8 = 0xD9;
+const VCOMH_DESELECT: u8 = 0xDB;
+const FADE_BLINK: u8 = 0x23;
+
+/// ### character ROM of the US2066
+#[derive(Clone, Copy)]
+pub enum CharacterRom {
+    A = 0x00,
+    B = 0x04,
+    C = 0x08,
+}
+
+/// ### fade out and blinking of the US2066
+/// `interval` (0 - 15) sets the speed, each step takes 8 x (interval + 1) frames
+#[derive(Clone, Copy)]
+pub enum Fade {
+    Disabled,
+    FadeOut(u8),
+    Blink(u8),
+}
+
+/// ### US2066 / SSD1311 character OLED profile
+/// internal regulator, contrast, fade out/blink, double height and character ROM.
+/// works over parallel interfaces, `NativeI2C` (same control bytes) and `SerialSPI`,
+/// use `LCD20X4_4LINE` for the 4 lines displays
+///
+/// ```ignore
+/// let mut oled = US2066::new(LiquidCrystal::new(&mut interface, Bus8Bits, LCD20X4_4LINE));
+/// oled.begin(&mut delay);
+/// oled.set_contrast(&mut delay, 0xFF);
+/// oled.write(&mut delay, Text("hello World!"));
+/// ```
+pub struct US2066<'interface, T: Interface, const COLS: u8, const LINES: usize> {
+    lcd: LiquidCrystal<'interface, T, COLS, LINES>,
+    internal_regulator: bool,
+    contrast: u8,
+    rom: CharacterRom,
+    double_height: bool,
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize>
+    US2066<'interface, T, COLS, LINES>
+{
+    /// default: internal regulator on (5V), contrast 0x7F, ROM A
+    pub fn new(
+        lcd: LiquidCrystal<'interface, T, COLS, LINES>,
+    ) -> US2066<'interface, T, COLS, LINES> {
+        US2066 {
+            lcd,
+            internal_regulator: true,
+            contrast: 0x7F,
+            rom: CharacterRom::A,
+            double_height: false,
+        }
+    }
+
+    /// ### returns the `LiquidCrystal`
+    pub fn release(self) -> LiquidCrystal<'interface, T, COLS, LINES> {
+        self.lcd
+    }
+
+    /// ### enable or disable the internal regulator
+    /// enable for 5V, disable for 3.3V. use before `begin`
+    pub fn set_internal_regulator(&mut self, state: bool) -> &mut Self {
+        self.internal_regulator = state;
+        self
+    }
+
+    /// ### initialization sequence of the US2066
+    pub fn begin(&mut self, delay: &mut impl DelayNs) -> &mut Self {
+        self.lcd.begin(delay);
+        let regulator = if self.internal_regulator { 0x5C } else { 0x00 };
+        let lines = if LINES > 2 { FOUR_LINES } else { 0x00 };
+        self.extended(delay, |lcd, delay| {
+            lcd.send(delay, FUNCTION_SELECTION_A, 0);
+            lcd.send(delay, regulator, RS);
+        });
+        self.lcd.send(delay, 0x08, 0); //display off
+        self.oled_commands(delay, &[CLOCK_DIVIDE, 0x70]);
+        let rom = self.rom as u8;
+        self.extended(delay, |lcd, delay| {
+            lcd.send(delay, EXTENDED_FUNCTION_SET | lines, 0);
+            lcd.send(delay, ENTRY_MODE_COM_SEG, 0);
+            lcd.send(delay, FUNCTION_SELECTION_B, 0);
+            lcd.send(delay, rom, RS);
+        });
+        let contrast = self.contrast;
+        self.oled_commands(
+            delay,
+            &[
+                SEG_PINS,
+                0x10,
+                FUNCTION_SELECTION_C,
+                0x00,
+                CONTRAST,
+                contrast,
+                PHASE_LENGTH,
+                0xF1,
+                VCOMH_DESELECT,
+                0x40,
+            ],
+        );
+        self.lcd.write(delay, SendType::Command(Clear));
+        self.lcd.update_config(delay);
+        self
+    }
+
+    /// ### sets the contrast (0 - 255)
+    pub fn set_contrast(&mut self, delay: &mut impl DelayNs, contrast: u8) -> &mut Self {
+        self.contrast = contrast;
+        self.oled_commands(delay, &[CONTRAST, contrast]);
+        self
+    }
+
+    /// ### fade out or blink the display
+    pub fn set_fade(&mut self, delay: &mut impl DelayNs, fade: Fade) -> &mut Self {
+        let mode = match fade {
+            Fade::Disabled => 0x00,
+            Fade::FadeOut(interval) => 0x20 | (interval & 0x0F),
+            Fade::Blink(interval) => 0x30 | (interval & 0x0F),
+        };
+        self.oled_commands(delay, &[FADE_BLINK, mode]);
+        self
+    }
+
+    /// ### enables double height on the selected lines
+    pub fn enable_double_height(
+        &mut self,
+        delay: &mut impl DelayNs,
+        lines: DoubleHeight,
+    ) -> &mut Self {
+        self.double_height = true;
+        self.extended(delay, |lcd, delay| {
+            lcd.send(delay, DOUBLE_HEIGHT | lines as u8, 0);
+        });
+        self
+    }
+
+    /// ### all lines in normal height
+    pub fn disable_double_height(&mut self, delay: &mut impl DelayNs) -> &mut Self {
+        self.double_height = false;
+        self.extended(delay, |_, _| {});
+        self
+    }
+
+    /// ### selects the character ROM
+    /// the text already written is shown with the new ROM
+    pub fn set_rom(&mut self, delay: &mut impl DelayNs, rom: CharacterRom) -> &mut Self {
+        self.rom = rom;
+        self.extended(delay, |lcd, delay| {
+            lcd.send(delay, FUNCTION_SELECTION_B, 0);
+            lcd.send(delay, rom as u8, RS);
+        });
+        self
+    }
+
+    /// function set with RE = 0
+    fn function_set(&self) -> u8 {
+        if self.double_height {
+            self.lcd.function_set() | DH
+        } else {
+            self.lcd.function_set()
+        }
+    }
+
+    /// runs `f` with the extended function set (RE = 1)
+    fn extended<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        f: impl FnOnce(&mut LiquidCrystal<'interface, T, COLS, LINES>, &mut D),
+    ) {
+        let function_set = self.function_set();
+        self.lcd.send(delay, self.lcd.function_set() | RE, 0);
+        f(&mut self.lcd, delay);
+        self.lcd.send(delay, function_set, 0);
+    }
+
+    /// sends commands of the OLED command set (RE = 1, SD = 1)
+    fn oled_commands(&mut self, delay: &mut impl DelayNs, commands: &[u8]) {
+        self.extended(delay, |lcd, delay| {
+            lcd.send(delay, OLED_COMMANDS_ON, 0);
+            for command in commands {
+                lcd.send(delay, *command, 0);
+            }
+            lcd.send(delay, OLED_COMMANDS_OFF, 0);
+        });
+    }
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize> Deref
+    for US2066<'interface, T, COLS, LINES>
+{
+    type Target = LiquidCrystal<'interface, T, COLS, LINES>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.lcd
+    }
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize> DerefMut
+    for US2066<'interface, T, COLS, LINES>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.lcd
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::emulator::{MockInterface, VirtualDelay};
+    use crate::lcd_trait::{BusBits, LCD20X4_4LINE};
+
+    #[test]
+    fn begin_sequence() {
+        let mut delay = VirtualDelay::default();
+        let mut mock = MockInterface::new();
+        US2066::new(LiquidCrystal::new(
+            &mut mock,
+            BusBits::Bus8Bits,
+            LCD20X4_4LINE,
+        ))
+        .begin(&mut delay);
+
+        mock.expect_commands(&[
+            0x30, 0x30, 0x30, 0x38, 0x01, 0x02, 0x0C, 0x06, // LiquidCrystal::begin
+            0x3A, 0x71, 0x38, // internal regulator
+            0x08, // display off
+            0x3A, 0x79, 0xD5, 0x70, 0x78, 0x38, // clock divider
+            0x3A, 0x09, 0x06, 0x72, 0x38, // 4 lines, COM/SEG direction, ROM
+            0x3A, 0x79, 0xDA, 0x10, 0xDC, 0x00, 0x81, 0x7F, 0xD9, 0xF1, 0xDB, 0x40, 0x78, 0x38,
+            0x01, 0x0C, 0x06,
+        ]);
+        // regulator on (5V), ROM A
+        assert_eq!(mock.data(), [0x5C, 0x00]);
+    }
+
+    #[test]
+    fn regulator_off() {
+        let mut delay = VirtualDelay::default();
+        let mut mock = MockInterface::new();
+        US2066::new(LiquidCrystal::new(
+            &mut mock,
+            BusBits::Bus8Bits,
+            LCD20X4_4LINE,
+        ))
+        .set_internal_regulator(false)
+        .begin(&mut delay);
+        assert_eq!(mock.data(), [0x00, 0x00]);
+    }
+}
@@ -0,0 +1,92 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::lcd_trait::{DelayNs, Interface, LiquidCrystal, RS};
+
+const POWER_ON: u8 = 0x17;
+const POWER_OFF: u8 = 0x13;
+
+/// ### brightness of the VFD
+#[derive(Clone, Copy)]
+pub enum Brightness {
+    Percent100 = 0x00,
+    Percent75 = 0x01,
+    Percent50 = 0x02,
+    Percent25 = 0x03,
+}
+
+/// ### VFD profile (Noritake CU series and compatibles)
+/// the brightness is the data write that follows the function set.
+///
+/// ```ignore
+/// let mut vfd = VFD::new(LiquidCrystal::new(&mut interface, Bus4Bits, LCD20X4));
+/// vfd.begin(&mut delay);
+/// vfd.set_brightness(&mut delay, Brightness::Percent50);
+/// ```
+pub struct VFD<'interface, T: Interface, const COLS: u8, const LINES: usize> {
+    lcd: LiquidCrystal<'interface, T, COLS, LINES>,
+    brightness: Brightness,
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize> VFD<'interface, T, COLS, LINES> {
+    pub fn new(lcd: LiquidCrystal<'interface, T, COLS, LINES>) -> VFD<'interface, T, COLS, LINES> {
+        VFD {
+            lcd,
+            brightness: Brightness::Percent100,
+        }
+    }
+
+    /// ### returns the `LiquidCrystal`
+    pub fn release(self) -> LiquidCrystal<'interface, T, COLS, LINES> {
+        self.lcd
+    }
+
+    /// ### initialization sequence with the current brightness
+    pub fn begin(&mut self, delay: &mut impl DelayNs) -> &mut Self {
+        self.lcd.begin(delay);
+        let brightness = self.brightness;
+        self.set_brightness(delay, brightness);
+        self
+    }
+
+    /// ### sets the brightness
+    pub fn set_brightness(
+        &mut self,
+        delay: &mut impl DelayNs,
+        brightness: Brightness,
+    ) -> &mut Self {
+        self.brightness = brightness;
+        let function_set = self.lcd.function_set();
+        self.lcd.send(delay, function_set, 0);
+        self.lcd.send(delay, brightness as u8, RS);
+        self
+    }
+
+    /// ### power down mode
+    /// turns off the filament and the high voltage, the DDRAM is kept
+    pub fn set_power(&mut self, delay: &mut impl DelayNs, state: bool) -> &mut Self {
+        if state {
+            self.lcd.send(delay, POWER_ON, 0);
+        } else {
+            self.lcd.send(delay, POWER_OFF, 0);
+        }
+        self
+    }
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize> Deref
+    for VFD<'interface, T, COLS, LINES>
+{
+    type Target = LiquidCrystal<'interface, T, COLS, LINES>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.lcd
+    }
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize> DerefMut
+    for VFD<'interface, T, COLS, LINES>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.lcd
+    }
+}
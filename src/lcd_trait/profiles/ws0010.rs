@@ -0,0 +1,261 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::lcd_trait::{BusBits, DelayNs, Interface, InterfaceKind, LiquidCrystal, RS};
+
+/// cursor/display shift with graphic mode (G/C) and internal power (PWR) bits
+const MODE_POWER: u8 = 0x13;
+const GRAPHIC: u8 = 0x08;
+const POWER_ON: u8 = 0x04;
+
+/// ### font tables of the WS0010
+#[derive(Clone, Copy)]
+pub enum FontTable {
+    EnglishJapanese = 0x00,
+    WesternEuropean1 = 0x01,
+    EnglishRussian = 0x02,
+    WesternEuropean2 = 0x03,
+}
+
+/// ### frame buffer for the graphic mode
+/// `W` columns of pixels and `PAGES` rows of 8 pixels (each byte is a column of 8 pixels, bit 0 on top).
+/// a 16x2 display is 100 x 16 (`GraphicBuffer<100, 2>`)
+pub struct GraphicBuffer<const W: usize, const PAGES: usize> {
+    pub pages: [[u8; W]; PAGES],
+}
+
+impl<const W: usize, const PAGES: usize> GraphicBuffer<W, PAGES> {
+    pub fn new() -> GraphicBuffer<W, PAGES> {
+        GraphicBuffer {
+            pages: [[0; W]; PAGES],
+        }
+    }
+
+    /// ### turns a pixel on or off
+    pub fn set_pixel(&mut self, x: usize, y: usize, state: bool) {
+        if x < W && y < PAGES * 8 {
+            if state {
+                self.pages[y / 8][x] |= 1 << (y % 8);
+            } else {
+                self.pages[y / 8][x] &= !(1 << (y % 8));
+            }
+        }
+    }
+
+    /// ### returns the state of a pixel
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        x < W && y < PAGES * 8 && (self.pages[y / 8][x] & (1 << (y % 8))) != 0
+    }
+
+    /// ### turns all pixels off
+    pub fn clear(&mut self) {
+        self.pages = [[0; W]; PAGES];
+    }
+}
+
+impl<const W: usize, const PAGES: usize> Default for GraphicBuffer<W, PAGES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// ### Winstar WS0010 OLED profile
+/// power on sequence with resynchronization, font tables and graphic mode.
+///
+/// the WS0010 takes 6.2 ms to clear the display, wait after `Clear`
+///
+/// ```ignore
+/// let mut oled = WS0010::new(LiquidCrystal::new(&mut interface, Bus4Bits, LCD16X2));
+/// oled.begin(&mut delay, FontTable::WesternEuropean1);
+/// oled.write(&mut delay, Text("hello World!"));
+/// ```
+pub struct WS0010<'interface, T: Interface, const COLS: u8, const LINES: usize> {
+    lcd: LiquidCrystal<'interface, T, COLS, LINES>,
+    font: FontTable,
+    graphic: bool,
+    power: bool,
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize>
+    WS0010<'interface, T, COLS, LINES>
+{
+    pub fn new(
+        lcd: LiquidCrystal<'interface, T, COLS, LINES>,
+    ) -> WS0010<'interface, T, COLS, LINES> {
+        WS0010 {
+            lcd,
+            font: FontTable::EnglishJapanese,
+            graphic: false,
+            power: true,
+        }
+    }
+
+    /// ### returns the `LiquidCrystal`
+    pub fn release(self) -> LiquidCrystal<'interface, T, COLS, LINES> {
+        self.lcd
+    }
+
+    /// ### initialization sequence of the WS0010
+    /// works after power on and after a reset of the MCU (warm start):
+    /// in 4 bits the bus is resynchronized with 5 null nibbles, then put back in 8 bits and 4 bits
+    pub fn begin(&mut self, delay: &mut impl DelayNs, font: FontTable) -> &mut Self {
+        self.font = font;
+        self.graphic = false;
+        self.power = true;
+        delay.delay_ms(100);
+        if T::KIND == InterfaceKind::Pins {
+            if let BusBits::Bus4Bits = self.lcd.bus {
+                for _ in 0..5 {
+                    self.lcd.send8bits(delay, 0x00, 0);
+                    delay.delay_ms(1);
+                }
+            }
+            self.lcd.send8bits(delay, 0x30, 0);
+            delay.delay_ms(5);
+            self.lcd.send8bits(delay, 0x30, 0);
+            delay.delay_ms(5);
+            if let BusBits::Bus4Bits = self.lcd.bus {
+                self.lcd.send8bits(delay, 0x20, 0);
+                delay.delay_ms(5);
+            }
+        }
+        self.set_font(delay, font);
+        self.lcd.send(delay, 0x08, 0); //display off
+        self.lcd.send(delay, 0x01, 0); //clear
+        delay.delay_us(6200);
+        self.lcd.send(delay, 0x06, 0); //entry mode
+        self.lcd.send(delay, 0x02, 0); //home
+        delay.delay_us(6200);
+        self.apply_mode(delay);
+        self.lcd.update_config(delay);
+        self
+    }
+
+    /// ### selects the font table
+    /// the text already written is shown with the new table
+    pub fn set_font(&mut self, delay: &mut impl DelayNs, font: FontTable) -> &mut Self {
+        self.font = font;
+        let function_set = self.lcd.function_set() | font as u8;
+        self.lcd.send(delay, function_set, 0);
+        self
+    }
+
+    /// ### turns the internal power on or off
+    /// the DDRAM is kept while the power is off
+    pub fn set_power(&mut self, delay: &mut impl DelayNs, state: bool) -> &mut Self {
+        self.power = state;
+        self.apply_mode(delay);
+        self
+    }
+
+    /// ### graphic mode
+    /// the display shows the graphic RAM instead of the text
+    pub fn enable_graphic_mode(&mut self, delay: &mut impl DelayNs) -> &mut Self {
+        self.graphic = true;
+        self.apply_mode(delay);
+        self
+    }
+
+    /// ### character mode (default)
+    pub fn enable_character_mode(&mut self, delay: &mut impl DelayNs) -> &mut Self {
+        self.graphic = false;
+        self.apply_mode(delay);
+        self
+    }
+
+    /// ### writes columns of 8 pixels in the graphic RAM
+    /// `page` is the row of 8 pixels, `x` the first column, each byte has bit 0 on top
+    pub fn write_graphic(
+        &mut self,
+        delay: &mut impl DelayNs,
+        page: u8,
+        x: u8,
+        columns: &[u8],
+    ) -> &mut Self {
+        self.lcd.send(delay, 0x80 | x, 0);
+        self.lcd.send(delay, 0x40 | page, 0);
+        for column in columns {
+            self.lcd.send(delay, *column, RS);
+        }
+        self
+    }
+
+    /// ### sends the frame buffer to the graphic RAM
+    pub fn draw<const W: usize, const PAGES: usize>(
+        &mut self,
+        delay: &mut impl DelayNs,
+        buffer: &GraphicBuffer<W, PAGES>,
+    ) -> &mut Self {
+        for (page, columns) in buffer.pages.iter().enumerate() {
+            self.write_graphic(delay, page as u8, 0, columns);
+        }
+        self
+    }
+
+    fn apply_mode(&mut self, delay: &mut impl DelayNs) {
+        let mut mode = MODE_POWER;
+        if self.graphic {
+            mode |= GRAPHIC;
+        }
+        if self.power {
+            mode |= POWER_ON;
+        }
+        self.lcd.send(delay, mode, 0);
+    }
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize> Deref
+    for WS0010<'interface, T, COLS, LINES>
+{
+    type Target = LiquidCrystal<'interface, T, COLS, LINES>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.lcd
+    }
+}
+
+impl<'interface, T: Interface, const COLS: u8, const LINES: usize> DerefMut
+    for WS0010<'interface, T, COLS, LINES>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.lcd
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::emulator::{MockInterface, VirtualDelay};
+    use crate::lcd_trait::LCD16X2;
+
+    #[test]
+    fn begin_sequence() {
+        let mut delay = VirtualDelay::default();
+        let mut mock = MockInterface::new();
+        WS0010::new(LiquidCrystal::new(&mut mock, BusBits::Bus4Bits, LCD16X2))
+            .begin(&mut delay, FontTable::WesternEuropean1);
+
+        mock.expect_commands(&[
+            0x00, 0x00, 0x00, 0x00, 0x00, // resynchronization, single nibbles
+            0x30, 0x30, 0x20, // 8 bits, then 4 bits
+            0x29, // function set with the font table
+            0x08, 0x01, 0x06, 0x02, // display off, clear, entry mode, home
+            0x17, // character mode, power on
+            0x0C, 0x06,
+        ]);
+    }
+
+    #[test]
+    fn modes() {
+        let mut delay = VirtualDelay::default();
+        let mut mock = MockInterface::new();
+        let mut oled = WS0010::new(LiquidCrystal::new(&mut mock, BusBits::Bus8Bits, LCD16X2));
+        oled.enable_graphic_mode(&mut delay)
+            .set_power(&mut delay, false)
+            .enable_character_mode(&mut delay)
+            .set_font(&mut delay, FontTable::EnglishRussian)
+            .write_graphic(&mut delay, 1, 10, &[0xFF, 0x81]);
+
+        mock.expect_commands(&[0x1F, 0x1B, 0x13, 0x3A, 0x8A, 0x41]);
+        assert_eq!(mock.data(), [0xFF, 0x81]);
+    }
+}
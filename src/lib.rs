@@ -1,7 +1,9 @@
 //! # liquidCrystal
 //! a library to work with alphanumeric lcd display compatible with the HD44780 controller
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "std")]
+pub mod emulator;
 pub mod lcd_trait;
 pub mod prelude;
 pub use lcd_trait::*;